@@ -1,4 +1,7 @@
-use crate::{cell::Cell, figure::Figure};
+use crate::{
+    cell::{Cell, Origin},
+    figure::Figure,
+};
 
 #[derive(Ord, Eq, PartialEq, PartialOrd, Debug, Clone, Hash)]
 pub enum Action {
@@ -28,7 +31,6 @@ impl Action {
                 let new_pencilmarks = old_pencilmarks
                     .iter()
                     .filter(|p| pencilmarks.contains(p) == preserve)
-                    .cloned()
                     .collect();
 
                 grid.set_pencilmarks(i, new_pencilmarks);
@@ -38,7 +40,7 @@ impl Action {
     pub fn apply_to_grid(&self, grid: &mut crate::grid::Grid) {
         match self {
             Action::PlaceNumber { position, number } => {
-                grid.set_number(*position, *number);
+                grid.place_number(*position, *number, Origin::Solver);
             }
             Action::RemovePencilmarks {
                 figure,
@@ -79,7 +81,7 @@ impl Action {
             } => {
                 for i in figure.clone() {
                     if let Cell::Pencilmarks(cell_pencilmarks) = &grid[i] {
-                        if !cell_pencilmarks.iter().all(|p| pencilmarks.contains(p)) {
+                        if !cell_pencilmarks.iter().all(|p| pencilmarks.contains(&p)) {
                             return true;
                         }
                     }
@@ -91,23 +93,21 @@ impl Action {
 
     /// Removes unnecessary positions from figure
     pub fn simplify(&mut self, grid: &crate::grid::Grid) {
-        let simplify_pencilmarks =
-            |figure: &mut Figure, pencilmarks: &Vec<u8>| -> Figure {
-                figure
-                    .clone()
-                    .into_iter()
-                    .filter(|pos| {
-                        if let Cell::Pencilmarks(p) = &grid[*pos] {
-                            p.iter().any(|cell_pencilmark| {
-                                pencilmarks.contains(cell_pencilmark)
-                            })
-                        } else {
-                            false
-                        }
-                    })
-                    .collect::<Vec<_>>()
-                    .into()
-            };
+        let simplify_pencilmarks = |figure: &mut Figure, pencilmarks: &Vec<u8>| -> Figure {
+            figure
+                .clone()
+                .into_iter()
+                .filter(|pos| {
+                    if let Cell::Pencilmarks(p) = &grid[*pos] {
+                        p.iter()
+                            .any(|cell_pencilmark| pencilmarks.contains(&cell_pencilmark))
+                    } else {
+                        false
+                    }
+                })
+                .collect::<Vec<_>>()
+                .into()
+        };
 
         match self {
             // can't simplify this
@@ -176,11 +176,11 @@ mod tests {
         action.apply_to_grid(&mut grid);
 
         assert_eq!(grid[0], Cell::Number(4));
-        assert_eq!(grid[1], Cell::Pencilmarks(vec![1, 6]));
-        assert_eq!(grid[2], Cell::Pencilmarks(vec![1, 6]));
-        assert_eq!(grid[3], Cell::Pencilmarks(vec![2, 5]));
-        assert_eq!(grid[4], Cell::Pencilmarks(vec![2, 5, 7]));
-        assert_eq!(grid[5], Cell::Pencilmarks(vec![2, 5, 7]));
+        assert_eq!(grid[1], Cell::Pencilmarks(vec![1, 6].into()));
+        assert_eq!(grid[2], Cell::Pencilmarks(vec![1, 6].into()));
+        assert_eq!(grid[3], Cell::Pencilmarks(vec![2, 5].into()));
+        assert_eq!(grid[4], Cell::Pencilmarks(vec![2, 5, 7].into()));
+        assert_eq!(grid[5], Cell::Pencilmarks(vec![2, 5, 7].into()));
         assert_eq!(grid[6], Cell::Number(9));
         assert_eq!(grid[7], Cell::Number(3));
         assert_eq!(grid[8], Cell::Number(8));
@@ -200,8 +200,8 @@ mod tests {
 
         action.apply_to_grid(&mut grid);
 
-        assert_eq!(grid[29], Cell::Pencilmarks(vec![2, 4]));
-        assert_eq!(grid[38], Cell::Pencilmarks(vec![2, 4]));
+        assert_eq!(grid[29], Cell::Pencilmarks(vec![2, 4].into()));
+        assert_eq!(grid[38], Cell::Pencilmarks(vec![2, 4].into()));
     }
 
     #[test]
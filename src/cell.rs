@@ -1,12 +1,44 @@
-#[derive(Debug, PartialEq, Clone)]
+use crate::pencilmarks::Pencilmarks;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum Cell {
     Number(u8),
-    Pencilmarks(Vec<u8>),
+    Pencilmarks(Pencilmarks),
+}
+
+/// Who placed the number into the cell.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Origin {
+    /// Number is a clue of the puzzle.
+    Given,
+    /// Number was placed by the solver (or any other code, applying [`crate::action::Action`]).
+    Solver,
+    /// Number was placed by hand.
+    User,
+}
+
+impl Origin {
+    /// Character, which is written before the number in text formats of the grid.
+    /// Givens have no prefix.
+    pub fn prefix(&self) -> Option<char> {
+        match self {
+            Origin::Given => None,
+            Origin::Solver => Some('~'),
+            Origin::User => Some('!'),
+        }
+    }
+
+    /// Returns origin, which is written with the given prefix.
+    pub fn from_prefix(c: char) -> Option<Origin> {
+        [Origin::Solver, Origin::User]
+            .into_iter()
+            .find(|origin| origin.prefix() == Some(c))
+    }
 }
 
 impl Cell {
     pub fn all_pencilmarks() -> Cell {
-        Cell::Pencilmarks(Vec::from([1, 2, 3, 4, 5, 6, 7, 8, 9]))
+        Cell::Pencilmarks(Pencilmarks::all())
     }
 
     pub fn is_number(&self) -> bool {
@@ -33,6 +65,7 @@ impl Cell {
         }
     }
 
+    /// Returns pencilmarks as numbers in ascending order.
     /// Panics if `self` is `Number`
     pub fn pencilmarks(&self) -> Vec<u8> {
         match self {
@@ -40,6 +73,14 @@ impl Cell {
             Cell::Pencilmarks(p) => p.to_vec(),
         }
     }
+
+    /// Returns pencilmarks of the cell, or empty pencilmarks if `self` is `Number`.
+    pub fn pencilmarks_mask(&self) -> Pencilmarks {
+        match self {
+            Cell::Number(_) => Pencilmarks::empty(),
+            Cell::Pencilmarks(p) => *p,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -49,7 +90,7 @@ mod tests {
     #[test]
     #[should_panic]
     fn number_unwrap() {
-        Cell::Pencilmarks([1, 2, 3].to_vec()).number();
+        Cell::Pencilmarks(vec![1, 2, 3].into()).number();
     }
 
     #[test]
@@ -60,7 +101,7 @@ mod tests {
 
     #[test]
     fn is_pencilmarks_or_number() {
-        let pencilmarks = Cell::Pencilmarks([1, 2, 3].to_vec());
+        let pencilmarks = Cell::Pencilmarks(vec![1, 2, 3].into());
         let number = Cell::Number(1);
 
         assert!(pencilmarks.is_pencilmarks());
@@ -69,4 +110,14 @@ mod tests {
         assert!(!number.is_pencilmarks());
         assert!(number.is_number());
     }
+
+    #[test]
+    fn origin_prefix() {
+        for origin in [Origin::Solver, Origin::User] {
+            assert_eq!(Origin::from_prefix(origin.prefix().unwrap()), Some(origin));
+        }
+
+        assert_eq!(Origin::Given.prefix(), None);
+        assert_eq!(Origin::from_prefix('+'), None);
+    }
 }
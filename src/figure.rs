@@ -1,15 +1,96 @@
 use std::{
-    collections::BTreeSet,
+    cmp::Ordering,
+    fmt::Debug,
     ops::{Add, AddAssign, Sub, SubAssign},
 };
 
 /// Figure can be row, column, square, or some set of positions.
-#[derive(Debug, PartialEq, Clone, PartialOrd, Eq, Ord, Hash)]
+#[derive(PartialEq, Clone, Eq, Hash, Default)]
 pub struct Figure {
-    // Choose BTreeSet here because iterating over its elements is deterministic
-    positions: BTreeSet<usize>,
+    // Bit `i` is set if position `i` is in the figure.
+    // Iterating over bits is deterministic (in ascending order).
+    positions: u128,
 }
 
+const fn row_mask(n: usize) -> u128 {
+    0b1_1111_1111 << (n * 9)
+}
+
+const fn col_mask(n: usize) -> u128 {
+    let mut res = 0;
+    let mut i = 0;
+
+    while i < 9 {
+        res |= 1 << (i * 9 + n);
+        i += 1;
+    }
+
+    res
+}
+
+const fn sqr_mask(n: usize) -> u128 {
+    let mut res = 0;
+    let mut i = 0;
+
+    while i < 9 {
+        res |= 1 << ((n / 3 * 3 + i / 3) * 9 + n % 3 * 3 + i % 3);
+        i += 1;
+    }
+
+    res
+}
+
+const ROWS: [u128; 9] = {
+    let mut res = [0; 9];
+    let mut n = 0;
+
+    while n < 9 {
+        res[n] = row_mask(n);
+        n += 1;
+    }
+
+    res
+};
+
+const COLS: [u128; 9] = {
+    let mut res = [0; 9];
+    let mut n = 0;
+
+    while n < 9 {
+        res[n] = col_mask(n);
+        n += 1;
+    }
+
+    res
+};
+
+const SQRS: [u128; 9] = {
+    let mut res = [0; 9];
+    let mut n = 0;
+
+    while n < 9 {
+        res[n] = sqr_mask(n);
+        n += 1;
+    }
+
+    res
+};
+
+/// Row, col and sqr of every cell (including the cell itself).
+const NEIGHBOURS: [u128; 81] = {
+    let mut res = [0; 81];
+    let mut i = 0;
+
+    while i < 81 {
+        res[i] = ROWS[i / 9] | COLS[i % 9] | SQRS[(i / 27) * 3 + (i % 9) / 3];
+        i += 1;
+    }
+
+    res
+};
+
+const ALL_CELLS: u128 = (1 << 81) - 1;
+
 impl Figure {
     pub fn row_checked(n: u8) -> Result<Figure, FigureNumberOutOfBoundError> {
         if n > 8 {
@@ -17,7 +98,7 @@ impl Figure {
         }
 
         Ok(Figure {
-            positions: BTreeSet::from_iter((0..9_usize).map(|i| i + n as usize * 9)),
+            positions: ROWS[n as usize],
         })
     }
     pub fn col_checked(n: u8) -> Result<Figure, FigureNumberOutOfBoundError> {
@@ -26,7 +107,7 @@ impl Figure {
         }
 
         Ok(Figure {
-            positions: BTreeSet::from_iter((0..9_usize).map(|i| i * 9 + n as usize)),
+            positions: COLS[n as usize],
         })
     }
     pub fn sqr_checked(n: u8) -> Result<Figure, FigureNumberOutOfBoundError> {
@@ -34,15 +115,8 @@ impl Figure {
             return Err(FigureNumberOutOfBoundError);
         }
 
-        const ZEROETH_SQUARE: [u8; 9] = [0, 1, 2, 9, 10, 11, 18, 19, 20];
-
         Ok(Figure {
-            positions: BTreeSet::from_iter(ZEROETH_SQUARE.into_iter().map(|i| {
-                let a = 3 * (n % 3);
-                let b = 27 * (n / 3);
-
-                (i + a + b) as usize
-            })),
+            positions: SQRS[n as usize],
         })
     }
 
@@ -50,11 +124,10 @@ impl Figure {
         if i >= 9 * 9 {
             return Err(CellIndexOutOfBoundError);
         }
-        let row = Self::row_of(i);
-        let col = Self::col_of(i);
-        let sqr = Self::sqr_of(i);
 
-        Ok(Figure::row(row) + Figure::col(col) + Figure::sqr(sqr))
+        Ok(Figure {
+            positions: NEIGHBOURS[i],
+        })
     }
 
     /// Panics if n > 8
@@ -79,7 +152,7 @@ impl Figure {
 
     pub fn all_cells() -> Figure {
         Figure {
-            positions: BTreeSet::from_iter(0..81),
+            positions: ALL_CELLS,
         }
     }
 
@@ -134,98 +207,122 @@ impl Figure {
         Figure::sqr_of_checked(i).unwrap()
     }
 
+    /// Returns `Some(n)` if figure is not empty and all positions lay in `table[n]`.
+    fn is_inside_of(&self, table: &[u128; 9], n: u8) -> Option<u8> {
+        (!self.is_empty() && self.positions & !table[n as usize] == 0).then_some(n)
+    }
+
     /// Returns `Some(row_number)` if all positions lay on the same row
     /// Returns `None` otherwise
-    /// Panics if some of the cells are out of bounds of grid (> 80)
     pub fn is_on_the_same_row(&self) -> Option<u8> {
-        let mut res = None;
-
-        for i in self.clone() {
-            let row = Figure::row_of(i);
-
-            if res.is_none() {
-                res = Some(row);
-            } else if Some(row) != res {
-                return None;
-            }
-        }
-
-        res
+        self.first()
+            .and_then(|i| self.is_inside_of(&ROWS, Figure::row_of(i)))
     }
 
     /// Returns `Some(col_number)` if all positions lay on the same col
     /// Returns `None` otherwise
-    /// Panics if some of the cells are out of bounds of grid (> 80)
     pub fn is_on_the_same_col(&self) -> Option<u8> {
-        let mut res = None;
-
-        for i in self.clone() {
-            let col = Figure::col_of(i);
-
-            if res.is_none() {
-                res = Some(col);
-            } else if Some(col) != res {
-                return None;
-            }
-        }
-
-        res
+        self.first()
+            .and_then(|i| self.is_inside_of(&COLS, Figure::col_of(i)))
     }
 
     /// Returns `Some(sqr_number)` if all positions lay on the same sqr
     /// Returns `None` otherwise
-    /// Panics if some of the cells are out of bounds of grid (> 80)
     pub fn is_on_the_same_sqr(&self) -> Option<u8> {
-        let mut res = None;
-
-        for i in self.clone() {
-            let sqr = Figure::sqr_of(i);
+        self.first()
+            .and_then(|i| self.is_inside_of(&SQRS, Figure::sqr_of(i)))
+    }
 
-            if res.is_none() {
-                res = Some(sqr);
-            } else if Some(sqr) != res {
-                return None;
-            }
+    /// Returns figure with positions, which are both in `self` and `other` figure.
+    pub fn intersection(&self, other: Figure) -> Figure {
+        Figure {
+            positions: self.positions & other.positions,
         }
-
-        res
     }
 
     /// Returns figure with positions from `self` and `other` figure.
-    pub fn intersection(&self, other: Figure) -> Figure {
+    /// Same as `self + other`.
+    pub fn union(&self, other: Figure) -> Figure {
         Figure {
-            positions: self
-                .positions
-                .intersection(&other.positions)
-                .cloned()
-                .collect(),
+            positions: self.positions | other.positions,
+        }
+    }
+
+    /// Returns figure with positions from `self`, which are not in `other` figure.
+    /// Same as `self - other`.
+    pub fn difference(&self, other: Figure) -> Figure {
+        Figure {
+            positions: self.positions & !other.positions,
+        }
+    }
+
+    /// Panics if i > 80
+    pub fn contains(&self, i: usize) -> bool {
+        assert!(i < 9 * 9, "cell index is out of bounds (0..81)");
+        self.positions & (1 << i) != 0
+    }
+
+    /// Adds the position to the figure.
+    /// Panics if i > 80
+    pub fn insert(&mut self, i: usize) {
+        assert!(i < 9 * 9, "cell index is out of bounds (0..81)");
+        self.positions |= 1 << i;
+    }
+
+    /// Returns the smallest position of the figure.
+    pub fn first(&self) -> Option<usize> {
+        (!self.is_empty()).then(|| self.positions.trailing_zeros() as usize)
+    }
+
+    /// Iterates over positions in ascending order.
+    pub fn iter(&self) -> IntoIter {
+        IntoIter {
+            positions: self.positions,
         }
     }
 
     /// Returns the number of positions in current figure.
     pub fn len(&self) -> usize {
-        self.positions.len()
+        self.positions.count_ones() as usize
     }
 
     /// Returns true if figure has no positions in it.
     pub fn is_empty(&self) -> bool {
-        self.len() == 0
+        self.positions == 0
+    }
+}
+
+/// Figures are compared as sorted lists of their positions.
+impl Ord for Figure {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl PartialOrd for Figure {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Debug for Figure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
     }
 }
 
 impl Add for Figure {
     type Output = Self;
 
-    fn add(mut self, mut rhs: Self) -> Self::Output {
-        self.positions.append(&mut rhs.positions);
-
-        self
+    fn add(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
     }
 }
 
 impl AddAssign for Figure {
-    fn add_assign(&mut self, mut rhs: Self) {
-        self.positions.append(&mut rhs.positions);
+    #[allow(clippy::suspicious_op_assign_impl)]
+    fn add_assign(&mut self, rhs: Self) {
+        self.positions |= rhs.positions;
     }
 }
 
@@ -233,33 +330,96 @@ impl Sub for Figure {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        Figure {
-            positions: self.positions.difference(&rhs.positions).cloned().collect(),
-        }
+        self.difference(rhs)
     }
 }
 
 impl SubAssign for Figure {
+    #[allow(clippy::suspicious_op_assign_impl)]
     fn sub_assign(&mut self, rhs: Self) {
-        self.positions = self.positions.difference(&rhs.positions).cloned().collect();
+        self.positions &= !rhs.positions;
+    }
+}
+
+/// Iterator over positions of [`Figure`] in ascending order.
+#[derive(Debug, Clone)]
+pub struct IntoIter {
+    positions: u128,
+}
+
+impl Iterator for IntoIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.positions == 0 {
+            return None;
+        }
+
+        let i = self.positions.trailing_zeros() as usize;
+        self.positions &= self.positions - 1;
+
+        Some(i)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.positions.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for IntoIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.positions == 0 {
+            return None;
+        }
+
+        let i = 127 - self.positions.leading_zeros() as usize;
+        self.positions &= !(1 << i);
+
+        Some(i)
     }
 }
 
+impl ExactSizeIterator for IntoIter {}
+
 impl IntoIterator for Figure {
     type Item = usize;
 
-    type IntoIter = std::collections::btree_set::IntoIter<Self::Item>;
+    type IntoIter = IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.positions.into_iter()
+        self.iter()
+    }
+}
+
+impl IntoIterator for &Figure {
+    type Item = usize;
+
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<usize> for Figure {
+    /// Panics if some position is out of bounds of grid (> 80)
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut positions = 0;
+
+        for i in iter {
+            assert!(i < 9 * 9, "cell index is out of bounds (0..81)");
+            positions |= 1 << i;
+        }
+
+        Figure { positions }
     }
 }
 
 impl From<Vec<usize>> for Figure {
+    /// Panics if some position is out of bounds of grid (> 80)
     fn from(value: Vec<usize>) -> Self {
-        Figure {
-            positions: BTreeSet::from_iter(value),
-        }
+        value.into_iter().collect()
     }
 }
 
@@ -296,16 +456,12 @@ mod tests {
 
         assert_eq!(
             second_row,
-            Figure {
-                positions: [9, 10, 11, 12, 13, 14, 15, 16, 17].into()
-            }
+            Figure::from(vec![9, 10, 11, 12, 13, 14, 15, 16, 17])
         );
 
         assert_eq!(
             ninth_row,
-            Figure {
-                positions: [72, 73, 74, 75, 76, 77, 78, 79, 80].into()
-            }
+            Figure::from(vec![72, 73, 74, 75, 76, 77, 78, 79, 80])
         );
     }
 
@@ -316,16 +472,12 @@ mod tests {
 
         assert_eq!(
             second_col,
-            Figure {
-                positions: [1, 10, 19, 28, 37, 46, 55, 64, 73].into()
-            }
+            Figure::from(vec![1, 10, 19, 28, 37, 46, 55, 64, 73])
         );
 
         assert_eq!(
             ninth_col,
-            Figure {
-                positions: [8, 17, 26, 35, 44, 53, 62, 71, 80].into()
-            }
+            Figure::from(vec![8, 17, 26, 35, 44, 53, 62, 71, 80])
         );
     }
 
@@ -336,16 +488,12 @@ mod tests {
 
         assert_eq!(
             second_sqr,
-            Figure {
-                positions: [3, 4, 5, 12, 13, 14, 21, 22, 23].into()
-            }
+            Figure::from(vec![3, 4, 5, 12, 13, 14, 21, 22, 23])
         );
 
         assert_eq!(
             ninth_sqr,
-            Figure {
-                positions: [60, 61, 62, 69, 70, 71, 78, 79, 80].into()
-            }
+            Figure::from(vec![60, 61, 62, 69, 70, 71, 78, 79, 80])
         );
     }
 
@@ -415,4 +563,48 @@ mod tests {
             .is_on_the_same_sqr()
             .is_none());
     }
+
+    #[test]
+    fn set_operations() {
+        let a: Figure = vec![0, 1, 2, 80].into();
+        let b: Figure = vec![2, 3, 80].into();
+
+        assert_eq!(a.union(b.clone()), vec![0, 1, 2, 3, 80].into());
+        assert_eq!(a.intersection(b.clone()), vec![2, 80].into());
+        assert_eq!(a.difference(b.clone()), vec![0, 1].into());
+        assert_eq!(a.clone() + b.clone(), a.union(b.clone()));
+        assert_eq!(a.clone() - b.clone(), a.difference(b));
+
+        assert!(a.contains(80));
+        assert!(!a.contains(3));
+        assert_eq!(a.first(), Some(0));
+        assert_eq!(Figure::default().first(), None);
+        assert_eq!(a.iter().rev().collect::<Vec<_>>(), vec![80, 2, 1, 0]);
+        assert_eq!(Figure::all_cells().len(), 81);
+    }
+
+    #[test]
+    fn neighbours_are_units_of_the_cell() {
+        for i in 0..81 {
+            let row = Figure::row_of(i);
+            let col = Figure::col_of(i);
+            let sqr = Figure::sqr_of(i);
+
+            assert!(Figure::row(row).contains(i));
+            assert!(Figure::col(col).contains(i));
+            assert!(Figure::sqr(sqr).contains(i));
+            assert_eq!(Figure::neighbours(i).len(), 21);
+        }
+    }
+
+    #[test]
+    fn ordered_as_sorted_positions() {
+        let a: Figure = vec![1, 80].into();
+        let b: Figure = vec![2].into();
+        let c: Figure = vec![1, 3].into();
+
+        assert!(a < b);
+        assert!(c < a);
+        assert!(Figure::from(vec![1]) < c);
+    }
 }
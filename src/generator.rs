@@ -0,0 +1,277 @@
+use std::str::FromStr;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    figure::Figure,
+    grading::{grade, Grade, Tier},
+    grid::Grid,
+    solver::{brute_force, Solver},
+};
+
+/// Symmetry of the givens in generated puzzle.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Symmetry {
+    None,
+    /// Rotation by 180 degrees around the center cell.
+    Rotational,
+    /// Reflection over the middle column.
+    Mirror,
+    /// Reflection over the main (top left to bottom right) diagonal.
+    Diagonal,
+}
+
+impl Symmetry {
+    /// Returns position and its symmetric position (if it is different).
+    fn orbit(&self, pos: usize) -> Vec<usize> {
+        let row = Figure::row_of(pos) as usize;
+        let col = Figure::col_of(pos) as usize;
+
+        let image = match self {
+            Symmetry::None => pos,
+            Symmetry::Rotational => 80 - pos,
+            Symmetry::Mirror => row * 9 + (8 - col),
+            Symmetry::Diagonal => col * 9 + row,
+        };
+
+        if image == pos {
+            vec![pos]
+        } else {
+            vec![pos, image]
+        }
+    }
+
+    /// Returns every orbit of the grid, each one exactly once.
+    fn orbits(&self) -> Vec<Vec<usize>> {
+        Figure::all_cells()
+            .into_iter()
+            .map(|pos| self.orbit(pos))
+            .filter(|orbit| orbit.iter().all(|&other| other >= orbit[0]))
+            .collect()
+    }
+}
+
+/// Generates puzzles with unique solution.
+/// If tier or required methods are set, keeps only puzzles, whose logical solve
+/// with the generator's [`Solver`] matches them.
+///
+/// Generation is fully defined by the given random number generator,
+/// so seedable RNG (like [`rand::rngs::StdRng`]) makes it reproducible.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    solver: Solver,
+    symmetry: Symmetry,
+    tier: Option<Tier>,
+    required_methods: Vec<String>,
+    max_attempts: usize,
+}
+
+impl Default for Generator {
+    fn default() -> Self {
+        Self {
+            solver: Solver::all_methods(),
+            symmetry: Symmetry::None,
+            tier: None,
+            required_methods: vec![],
+            max_attempts: 100,
+        }
+    }
+}
+
+impl Generator {
+    /// Returns generator of puzzles of any difficulty, without symmetry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Solver, used for grading the puzzles.
+    pub fn solver(mut self, solver: Solver) -> Self {
+        self.solver = solver;
+        self
+    }
+
+    pub fn symmetry(mut self, symmetry: Symmetry) -> Self {
+        self.symmetry = symmetry;
+        self
+    }
+
+    /// Generated puzzle will have exactly this tier.
+    pub fn tier(mut self, tier: Tier) -> Self {
+        self.tier = Some(tier);
+        self
+    }
+
+    /// Generated puzzle's solve will use every method with given names (their `Display`).
+    pub fn required_methods(mut self, names: Vec<String>) -> Self {
+        self.required_methods = names;
+        self
+    }
+
+    /// How many puzzles to try before giving up.
+    pub fn max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Returns puzzle, matching every requirement.
+    /// Returns `None` if no such puzzle was found in `max_attempts` attempts.
+    pub fn generate<R: Rng>(&self, rng: &mut R) -> Option<Grid> {
+        for _ in 0..self.max_attempts {
+            let puzzle = self.minimize(&full_grid(rng), rng);
+
+            if self.matches(&grade(&puzzle, &self.solver)) {
+                return Some(puzzle);
+            }
+        }
+
+        None
+    }
+
+    fn matches(&self, grade: &Grade) -> bool {
+        self.tier.is_none_or(|tier| grade.tier() == tier)
+            && self.required_methods.iter().all(|name| {
+                grade
+                    .usages()
+                    .iter()
+                    .any(|usage| usage.name() == name.as_str())
+            })
+    }
+
+    /// Removes givens from solved grid (in random order, respecting symmetry) while
+    /// the puzzle has unique solution and is not harder than requested tier.
+    fn minimize<R: Rng>(&self, solution: &Grid, rng: &mut R) -> Grid {
+        let mut givens: Vec<u8> = Figure::all_cells()
+            .into_iter()
+            .map(|pos| solution[pos].number())
+            .collect();
+        let mut puzzle = to_puzzle(&givens);
+
+        let mut orbits = self.symmetry.orbits();
+        orbits.shuffle(rng);
+
+        for orbit in orbits {
+            let removed: Vec<u8> = orbit.iter().map(|&pos| givens[pos]).collect();
+
+            for &pos in &orbit {
+                givens[pos] = 0;
+            }
+
+            let candidate = to_puzzle(&givens);
+
+            let is_acceptable = candidate.has_unique_solution()
+                && self
+                    .tier
+                    .is_none_or(|tier| grade(&candidate, &self.solver).tier() <= tier);
+
+            if is_acceptable {
+                puzzle = candidate;
+            } else {
+                for (&pos, number) in orbit.iter().zip(removed) {
+                    givens[pos] = number;
+                }
+            }
+        }
+
+        puzzle
+    }
+}
+
+/// Returns random full valid grid.
+pub fn full_grid<R: Rng>(rng: &mut R) -> Grid {
+    brute_force::solve_random(&to_puzzle(&[0; 81]), rng).expect("empty grid always has a solution")
+}
+
+fn to_puzzle(givens: &[u8]) -> Grid {
+    let line: String = givens.iter().map(|n| n.to_string()).collect();
+
+    Grid::from_str(&line).expect("givens are always 81 digits")
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::cell::Cell;
+
+    use super::*;
+
+    fn givens(grid: &Grid) -> Vec<bool> {
+        Figure::all_cells()
+            .into_iter()
+            .map(|pos| grid[pos].is_number())
+            .collect()
+    }
+
+    #[test]
+    fn unique_and_reproducible() {
+        let generator = Generator::new();
+
+        let puzzle = generator.generate(&mut StdRng::seed_from_u64(1)).unwrap();
+        let same_puzzle = generator.generate(&mut StdRng::seed_from_u64(1)).unwrap();
+        let other_puzzle = generator.generate(&mut StdRng::seed_from_u64(2)).unwrap();
+
+        assert!(puzzle.has_unique_solution());
+        assert_eq!(puzzle, same_puzzle);
+        assert_ne!(puzzle, other_puzzle);
+    }
+
+    #[test]
+    fn symmetric() {
+        let mut rng = StdRng::seed_from_u64(3);
+
+        for symmetry in [Symmetry::Rotational, Symmetry::Mirror, Symmetry::Diagonal] {
+            let puzzle = Generator::new()
+                .symmetry(symmetry)
+                .generate(&mut rng)
+                .unwrap();
+            let givens = givens(&puzzle);
+
+            assert!(puzzle.has_unique_solution());
+
+            for pos in Figure::all_cells() {
+                for other in symmetry.orbit(pos) {
+                    assert_eq!(givens[pos], givens[other]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn with_tier() {
+        let mut rng = StdRng::seed_from_u64(4);
+
+        let puzzle = Generator::new()
+            .tier(Tier::Easy)
+            .generate(&mut rng)
+            .unwrap();
+
+        assert!(puzzle.has_unique_solution());
+        assert_eq!(grade(&puzzle, &Solver::all_methods()).tier(), Tier::Easy);
+    }
+
+    #[test]
+    fn with_required_methods() {
+        let mut rng = StdRng::seed_from_u64(5);
+
+        let puzzle = Generator::new()
+            .required_methods(vec!["Hidden Single".to_string()])
+            .generate(&mut rng)
+            .unwrap();
+
+        assert!(grade(&puzzle, &Solver::all_methods())
+            .usages()
+            .iter()
+            .any(|usage| usage.name() == "Hidden Single"));
+    }
+
+    #[test]
+    fn full_grid_is_solved() {
+        let grid = full_grid(&mut StdRng::seed_from_u64(6));
+
+        assert!(grid.is_solved());
+        assert!(Figure::all_cells()
+            .into_iter()
+            .all(|pos| matches!(grid[pos], Cell::Number(1..=9))));
+        assert_eq!(grid.count_solutions(2), 1);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    grid::Grid,
+    solver::{SolveError, Solver},
+};
+
+/// Difficulty tier of the puzzle.
+/// Tier of a solvable puzzle is the tier of the hardest method needed to solve it.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum Tier {
+    /// Only singles: difficulty up to 20.
+    Easy,
+    /// Subsets and intersections: difficulty up to 150.
+    Medium,
+    /// Fishes and simple chains: difficulty up to 300.
+    Hard,
+    /// Everything above.
+    Diabolical,
+    /// Solver can't finish the puzzle with enabled methods, or puzzle has contradiction.
+    UnsolvableByLogic,
+}
+
+impl Tier {
+    /// Returns tier of the method with the given [`crate::methods::Method::difficulty`].
+    pub fn from_difficulty(difficulty: u32) -> Tier {
+        match difficulty {
+            0..=20 => Tier::Easy,
+            21..=150 => Tier::Medium,
+            151..=300 => Tier::Hard,
+            _ => Tier::Diabolical,
+        }
+    }
+}
+
+impl std::fmt::Display for Tier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Tier::Easy => write!(f, "Easy"),
+            Tier::Medium => write!(f, "Medium"),
+            Tier::Hard => write!(f, "Hard"),
+            Tier::Diabolical => write!(f, "Diabolical"),
+            Tier::UnsolvableByLogic => write!(f, "Unsolvable by logic"),
+        }
+    }
+}
+
+/// How often method was used while solving.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MethodUsage {
+    name: String,
+    difficulty: u32,
+    count: usize,
+}
+
+impl MethodUsage {
+    /// Name of the method (its `Display`).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn difficulty(&self) -> u32 {
+        self.difficulty
+    }
+
+    /// Number of steps, made by this method.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// The first use costs full difficulty, every following use costs a quarter of it.
+    /// Method which is used often makes puzzle harder, but not as much as a new method.
+    fn score(&self) -> u32 {
+        self.difficulty + (self.count as u32 - 1) * self.difficulty / 4
+    }
+}
+
+/// Result of the grading.
+/// Grading idea is taken from sudokuwiki.org grader: every step adds the score of the method used.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grade {
+    score: u32,
+    tier: Tier,
+    givens: usize,
+    steps: Vec<String>,
+    usages: Vec<MethodUsage>,
+}
+
+impl Grade {
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn tier(&self) -> Tier {
+        self.tier
+    }
+
+    /// Number of givens (clues) in the graded grid.
+    pub fn givens(&self) -> usize {
+        self.givens
+    }
+
+    /// Names of the methods, used at each step (in order).
+    pub fn steps(&self) -> &Vec<String> {
+        &self.steps
+    }
+
+    /// Every used method (in order of the first use).
+    pub fn usages(&self) -> &Vec<MethodUsage> {
+        &self.usages
+    }
+}
+
+/// Solves the grid with the given solver and grades it by methods used.
+/// The grid itself is not changed.
+pub fn grade(grid: &Grid, solver: &Solver) -> Grade {
+    let givens = grid.givens_count();
+    let mut grid = grid.clone();
+    let result = solver.try_solve(&mut grid, false);
+    let is_solved = result.is_ok() && grid.is_solved();
+    let trace = result.unwrap_or_else(SolveError::into_trace);
+    let mut usages: Vec<MethodUsage> = vec![];
+
+    for step in trace.steps() {
+        if let Some(usage) = usages.iter_mut().find(|usage| usage.name == step.method()) {
+            usage.count += 1;
+        } else {
+            usages.push(MethodUsage {
+                name: step.method().to_string(),
+                difficulty: step.difficulty(),
+                count: 1,
+            });
+        }
+    }
+
+    let tier = if is_solved {
+        Tier::from_difficulty(
+            usages
+                .iter()
+                .map(|usage| usage.difficulty)
+                .max()
+                .unwrap_or(0),
+        )
+    } else {
+        Tier::UnsolvableByLogic
+    };
+
+    Grade {
+        score: usages.iter().map(MethodUsage::score).sum(),
+        tier,
+        givens,
+        steps: trace
+            .steps()
+            .iter()
+            .map(|step| step.method().to_string())
+            .collect(),
+        usages,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn grade_str(grid: &str) -> Grade {
+        grade(&Grid::from_str(grid).unwrap(), &Solver::all_methods())
+    }
+
+    #[test]
+    fn easy() {
+        let grade = grade_str(
+            "300967001040302080020000070070000090000873000500010003004705100905000207800621004",
+        );
+
+        assert_eq!(grade.tier(), Tier::Easy);
+        assert_eq!(grade.givens(), 32);
+        assert!(grade
+            .usages()
+            .iter()
+            .all(|usage| usage.name() == "Naked Single" || usage.name() == "Hidden Single"));
+        assert_eq!(
+            grade.steps().len(),
+            grade.usages().iter().map(MethodUsage::count).sum()
+        );
+    }
+
+    #[test]
+    fn harder_is_bigger() {
+        let easy = grade_str(
+            "300967001040302080020000070070000090000873000500010003004705100905000207800621004",
+        );
+        let medium = grade_str(
+            "400000938032094100095300240370609004529001673604703090957008300003900400240030709",
+        );
+        let hard = grade_str(
+            "100000569492056108056109240009640801064010000218035604040500016905061402621000005",
+        );
+
+        assert_eq!(medium.tier(), Tier::Medium);
+        assert_eq!(hard.tier(), Tier::Hard);
+        assert!(hard
+            .usages()
+            .iter()
+            .any(|usage| usage.name() == "X-Wing" && usage.count() == 1));
+        assert!(hard.usages().iter().all(|usage| usage.difficulty() > 0));
+
+        assert!(easy.score() < medium.score());
+        assert!(medium.score() < hard.score());
+    }
+
+    #[test]
+    fn unsolvable() {
+        let grade = grade_str(
+            "800000000003600000070090200050007000000045700000100030001000068008500010090000400",
+        );
+
+        assert_eq!(grade.tier(), Tier::UnsolvableByLogic);
+    }
+
+    #[test]
+    fn tiers() {
+        assert_eq!(Tier::from_difficulty(10), Tier::Easy);
+        assert_eq!(Tier::from_difficulty(60), Tier::Medium);
+        assert_eq!(Tier::from_difficulty(200), Tier::Hard);
+        assert_eq!(Tier::from_difficulty(500), Tier::Diabolical);
+    }
+}
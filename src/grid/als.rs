@@ -0,0 +1,118 @@
+use std::collections::BTreeSet;
+
+use itertools::Itertools;
+
+use crate::{cell::Cell, figure::Figure, pencilmarks::Pencilmarks};
+
+use super::Grid;
+
+/// Almost Locked Set: N cells of one unit with N+1 pencilmarks in total.
+/// If any of the pencilmarks is removed, the set becomes locked.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Als {
+    pub cells: Figure,
+    pub pencilmarks: Pencilmarks,
+}
+
+impl Als {
+    /// Returns cells of the set, which have the number in pencilmarks.
+    pub fn cells_with(&self, grid: &Grid, number: u8) -> Figure {
+        self.cells
+            .iter()
+            .filter(|&pos| grid[pos].pencilmarks_mask().contains(&number))
+            .collect()
+    }
+}
+
+impl Grid {
+    /// Returns every Almost Locked Set of the grid with up to `max_size` cells
+    /// (single bivalue cells included).
+    /// Set, which lies in several units, is returned once.
+    pub fn almost_locked_sets(&self, max_size: usize) -> Vec<Als> {
+        let mut found = BTreeSet::new();
+        let mut res = vec![];
+
+        for unit in Figure::all_figures() {
+            let cells: Vec<(usize, Pencilmarks)> = unit
+                .into_iter()
+                .filter_map(|pos| match self[pos] {
+                    Cell::Pencilmarks(pencilmarks) => Some((pos, pencilmarks)),
+                    Cell::Number(_) => None,
+                })
+                .collect();
+
+            let chosen_sets =
+                (1..=max_size.min(cells.len())).flat_map(|size| cells.iter().combinations(size));
+
+            for chosen in chosen_sets {
+                let mut figure = Figure::default();
+                let mut pencilmarks = Pencilmarks::empty();
+
+                for &(pos, cell_pencilmarks) in chosen {
+                    figure += vec![pos].into();
+                    pencilmarks += cell_pencilmarks;
+                }
+
+                if pencilmarks.len() == figure.len() + 1 && found.insert(figure.clone()) {
+                    res.push(Als {
+                        cells: figure,
+                        pencilmarks,
+                    });
+                }
+            }
+        }
+
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn almost_locked_sets() {
+        let mut grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        grid.set_pencilmarks(0, vec![3, 5]);
+        grid.set_pencilmarks(1, vec![3, 9]);
+
+        let sets = grid.almost_locked_sets(9);
+
+        for als in &sets {
+            assert_eq!(als.pencilmarks.len(), als.cells.len() + 1);
+            assert!(Figure::all_figures()
+                .iter()
+                .any(|unit| als.cells.difference(unit.clone()).is_empty()));
+        }
+
+        let cells: BTreeSet<Figure> = sets.iter().map(|als| als.cells.clone()).collect();
+        assert_eq!(cells.len(), sets.len());
+
+        // Only small sets are enumerated
+        let small = grid.almost_locked_sets(2);
+        assert!(small.iter().all(|als| als.cells.len() <= 2));
+        assert_eq!(
+            small.len(),
+            sets.iter().filter(|als| als.cells.len() <= 2).count()
+        );
+
+        // In the same row and sqr, but returned once
+        assert!(sets.contains(&Als {
+            cells: vec![0, 1].into(),
+            pencilmarks: vec![3, 5, 9].into(),
+        }));
+
+        // Every bivalue cell is an ALS
+        for pos in Figure::all_cells() {
+            if grid[pos].pencilmarks_mask().len() == 2 {
+                assert!(cells.contains(&vec![pos].into()));
+            }
+        }
+    }
+}
@@ -0,0 +1,383 @@
+use std::array;
+
+use crate::{
+    cell::{Cell, Origin},
+    figure::Figure,
+};
+
+use super::{Grid, ParseGridError};
+
+/// Characters, which are ignored in candidate grid, so that it can be drawn with borders.
+const BORDERS: [char; 6] = ['.', '-', ':', '\'', '|', '+'];
+
+impl Grid {
+    /// Parses bordered 9x9 candidate grid (as exported by HoDoKu or sudokuwiki.org):
+    /// ```text
+    /// .----------------.-------------.---------------.
+    /// | 4    67    1   | 279  278  3 | 679   5   29  |
+    /// ...
+    /// ```
+    /// Every cell is a group of digits. Cell with one digit is a number, every other
+    /// cell is pencilmarks. Borders (`.`, `-`, `:`, `'`, `|`, `+`) and whitespace are ignored.
+    /// Pencilmarks are kept exactly as given: they are not recalculated from numbers.
+    /// `0` is a cell without pencilmarks.
+    ///
+    /// Numbers are givens, unless prefixed with [`Origin::prefix`] (`~` or `!`).
+    pub fn from_candidate_grid(s: &str) -> Result<Grid, ParseGridError> {
+        // Digits of the cell and its origin, if it is prefixed
+        let mut cells = vec![];
+
+        for (line, line_str) in s.lines().enumerate() {
+            let mut cell: Option<(Vec<u8>, Option<Origin>)> = None;
+            let mut chars = line_str.chars().enumerate().peekable();
+
+            while let Some((column, c)) = chars.next() {
+                let invalid = ParseGridError::InvalidCharacter {
+                    line: line + 1,
+                    column: column + 1,
+                };
+
+                match c.to_digit(10) {
+                    Some(0) => {
+                        cell.get_or_insert_with(|| (Vec::new(), None));
+                    }
+                    Some(1..=9) => cell
+                        .get_or_insert_with(|| (Vec::new(), None))
+                        .0
+                        .push(c as u8 - b'0'),
+                    _ if c.is_whitespace() || BORDERS.contains(&c) => {
+                        cells.extend(cell.take());
+                    }
+                    _ => match Origin::from_prefix(c) {
+                        // Prefix is honoured only at the start of the cell and before a number
+                        Some(origin)
+                            if cell.is_none()
+                                && chars.peek().is_some_and(|(_, next)| next.is_ascii_digit()) =>
+                        {
+                            cell = Some((Vec::new(), Some(origin)));
+                        }
+                        _ => return Err(invalid),
+                    },
+                }
+            }
+
+            cells.extend(cell);
+        }
+
+        if cells.len() != 9 * 9 {
+            return Err(ParseGridError::InvalidSize(cells.len()));
+        }
+
+        let matrix = array::from_fn(|i| {
+            let mut digits = cells[i].0.clone();
+            digits.sort();
+            digits.dedup();
+
+            if digits.len() == 1 {
+                Cell::Number(digits[0])
+            } else {
+                Cell::Pencilmarks(digits.into())
+            }
+        });
+
+        let mut grid = Grid::with_givens(matrix);
+
+        for (i, (_, origin)) in cells.into_iter().enumerate() {
+            if origin.is_some() && grid.matrix[i].is_number() {
+                grid.origins[i] = origin;
+            }
+        }
+
+        Ok(grid)
+    }
+
+    /// Parses 729 characters: 9 for every cell, where `1` at the position `n`
+    /// means that number `n + 1` is possible in the cell, and `0` that it is not.
+    /// Cell with only one possible number is a given number.
+    /// Whitespace is ignored. For size errors, returns the number of characters found.
+    pub fn from_candidate_bitstring(s: &str) -> Result<Grid, ParseGridError> {
+        let mut bits = vec![];
+
+        for (line, line_str) in s.lines().enumerate() {
+            for (column, c) in line_str.chars().enumerate() {
+                match c {
+                    '0' => bits.push(false),
+                    '1' => bits.push(true),
+                    _ if c.is_whitespace() => (),
+                    _ => {
+                        return Err(ParseGridError::InvalidCharacter {
+                            line: line + 1,
+                            column: column + 1,
+                        })
+                    }
+                }
+            }
+        }
+
+        if bits.len() != 9 * 9 * 9 {
+            return Err(ParseGridError::InvalidSize(bits.len()));
+        }
+
+        let matrix = array::from_fn(|i| {
+            let digits: Vec<u8> = (1..=9).filter(|n| bits[i * 9 + *n as usize - 1]).collect();
+
+            if digits.len() == 1 {
+                Cell::Number(digits[0])
+            } else {
+                Cell::Pencilmarks(digits.into())
+            }
+        });
+
+        Ok(Grid::with_givens(matrix))
+    }
+
+    /// Returns bordered 9x9 candidate grid, which can be parsed by [`Grid::from_candidate_grid`].
+    /// Numbers, which are not givens, are prefixed with [`Origin::prefix`].
+    /// Cell with one pencilmark can't be told apart from a number in this format.
+    /// Cell without pencilmarks is written as `0`.
+    pub fn to_candidate_grid(&self) -> String {
+        let tokens: Vec<String> = Figure::all_cells()
+            .into_iter()
+            .map(|pos| match &self.matrix[pos] {
+                Cell::Number(n) => match self.origin(pos).and_then(|origin| origin.prefix()) {
+                    Some(prefix) => format!("{}{}", prefix, n),
+                    None => n.to_string(),
+                },
+                Cell::Pencilmarks(p) if p.is_empty() => "0".to_string(),
+                Cell::Pencilmarks(p) => p.iter().map(|n| n.to_string()).collect(),
+            })
+            .collect();
+
+        let widths: Vec<usize> = (0..9)
+            .map(|col| {
+                Figure::col(col)
+                    .into_iter()
+                    .map(|pos| tokens[pos].len())
+                    .max()
+                    .unwrap_or(1)
+            })
+            .collect();
+
+        // Cells are separated by two spaces, and one space is left before and after the box
+        let segment_width =
+            |b: usize| -> usize { widths[b * 3..b * 3 + 3].iter().sum::<usize>() + 6 };
+
+        let border = |left: char, middle: char, right: char| -> String {
+            let mut line = String::from(left);
+            for b in 0..3 {
+                line.push_str(&"-".repeat(segment_width(b)));
+                line.push(if b == 2 { right } else { middle });
+            }
+            line
+        };
+
+        let mut lines = vec![border('.', '.', '.')];
+
+        for row in 0..9 {
+            let mut line = String::from("|");
+
+            for b in 0..3 {
+                let cells: Vec<String> = (b * 3..b * 3 + 3)
+                    .map(|col| format!("{:<1$}", tokens[row * 9 + col], widths[col]))
+                    .collect();
+
+                line.push_str(&format!(" {} |", cells.join("  ")));
+            }
+
+            lines.push(line);
+
+            if row == 2 || row == 5 {
+                lines.push(border(':', '+', ':'));
+            }
+        }
+
+        lines.push(border('\'', '\'', '\''));
+
+        lines.join("\n")
+    }
+
+    /// Returns 729 characters, which can be parsed by [`Grid::from_candidate_bitstring`].
+    /// Cell with one pencilmark can't be told apart from a number in this format,
+    /// and every number is treated as given.
+    pub fn to_candidate_bitstring(&self) -> String {
+        self.matrix
+            .iter()
+            .flat_map(|cell| {
+                let digits = match cell {
+                    Cell::Number(n) => vec![*n],
+                    Cell::Pencilmarks(p) => p.to_vec(),
+                };
+
+                (1..=9).map(move |n| if digits.contains(&n) { '1' } else { '0' })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::solver::Solver;
+
+    use super::*;
+
+    fn mid_solve_grid() -> Grid {
+        let mut grid = Grid::from_str(
+            "000000700007109000680070010001090600000300021040000003008060100500000040000002005",
+        )
+        .unwrap();
+
+        grid.set_pencilmarks(7, vec![3, 5, 8, 9]);
+        grid.set_pencilmarks(8, vec![2, 4, 8, 9]);
+
+        grid
+    }
+
+    #[test]
+    fn candidate_grid_round_trip() {
+        let grid = mid_solve_grid();
+        let text = grid.to_candidate_grid();
+
+        assert_eq!(Grid::from_candidate_grid(&text), Ok(grid));
+    }
+
+    #[test]
+    fn candidate_grid_round_trip_without_pencilmarks() {
+        let mut grid = mid_solve_grid();
+        grid.set_pencilmarks(7, vec![]);
+        let text = grid.to_candidate_grid();
+
+        assert!(text.lines().nth(1).unwrap().contains(" 0 "));
+        assert_eq!(Grid::from_candidate_grid(&text), Ok(grid));
+    }
+
+    #[test]
+    fn candidate_bitstring_round_trip() {
+        let grid = mid_solve_grid();
+        let bits = grid.to_candidate_bitstring();
+
+        assert_eq!(bits.len(), 729);
+        assert_eq!(&bits[63..72], "001010011");
+        assert_eq!(&bits[72..81], "010100011");
+        assert_eq!(Grid::from_candidate_bitstring(&bits), Ok(grid));
+    }
+
+    #[test]
+    fn candidate_grid_format() {
+        let grid = Grid::from_str(
+            "000000700007109000680070010001090600000300021040000003008060100500000040000002005",
+        )
+        .unwrap();
+
+        assert_eq!(
+            grid.to_candidate_grid(),
+            "\
+.----------------------.---------------------.---------------------.
+| 12349  12359   23459 | 24568  23458  34568 | 7      35689  24689 |
+| 234    235     7     | 1      23458  9     | 23458  3568   2468  |
+| 6      8       23459 | 245    7      345   | 23459  1      249   |
+:----------------------+---------------------+---------------------:
+| 2378   2357    1     | 24578  9      4578  | 6      578    478   |
+| 789    5679    569   | 3      458    45678 | 4589   2      1     |
+| 2789   4       2569  | 25678  1258   15678 | 589    5789   3     |
+:----------------------+---------------------+---------------------:
+| 23479  2379    8     | 4579   6      3457  | 1      379    279   |
+| 5      123679  2369  | 789    138    1378  | 2389   4      26789 |
+| 13479  13679   3469  | 4789   1348   2     | 389    36789  5     |
+'----------------------'---------------------'---------------------'"
+        );
+    }
+
+    #[test]
+    fn candidate_grid_keeps_pencilmarks() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .-------------------.-------------------.-------------------.
+            | 4    1679  12679  | 139   2369  269   | 8     1239  5     |
+            | 26789 3    1256789| 14589 24569 245689| 12679 1249  124679|
+            | 2689 15689 125689 | 7     234569 245689| 12369 12349 123469|
+            :-------------------+-------------------+-------------------:
+            | 3789 2     15789  | 3459  34579 4579  | 13579 6     13789 |
+            | 3679 15679 15679  | 359   8     25679 | 4     12359 12379 |
+            | 36789 4    56789  | 359   1     25679 | 23579 23589 23789 |
+            :-------------------+-------------------+-------------------:
+            | 289  89    289    | 6     459   3     | 1259  7     12489 |
+            | 5    6789  3      | 2     479   1     | 69    489   4689  |
+            | 1    6789  4      | 589   579   5789  | 23569 23589 23689 |
+            '-------------------'-------------------'-------------------'
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(grid[0], Cell::Number(4));
+        // Not recalculated: 4 and 1 are still here
+        assert_eq!(grid[1], Cell::Pencilmarks(vec![1, 6, 7, 9].into()));
+        assert_eq!(grid[9], Cell::Pencilmarks(vec![2, 6, 7, 8, 9].into()));
+        assert_eq!(grid[80], Cell::Pencilmarks(vec![2, 3, 6, 8, 9].into()));
+    }
+
+    #[test]
+    fn candidate_errors() {
+        assert_eq!(
+            Grid::from_candidate_grid("| 1 2 3 |\n| 4 x 6 |"),
+            Err(ParseGridError::InvalidCharacter { line: 2, column: 5 })
+        );
+        assert_eq!(
+            Grid::from_candidate_grid("| 1 2 3 |\n| 4 5 6 |"),
+            Err(ParseGridError::InvalidSize(6))
+        );
+        assert_eq!(
+            Grid::from_candidate_grid("| 1 ! 3 |"),
+            Err(ParseGridError::InvalidCharacter { line: 1, column: 5 })
+        );
+        assert_eq!(
+            Grid::from_candidate_bitstring(&"1".repeat(728)),
+            Err(ParseGridError::InvalidSize(728))
+        );
+        assert_eq!(
+            Grid::from_candidate_bitstring("0012"),
+            Err(ParseGridError::InvalidCharacter { line: 1, column: 4 })
+        );
+    }
+
+    #[test]
+    fn solver_continues_from_parsed_grid() {
+        let grid = mid_solve_grid();
+        let mut parsed = Grid::from_candidate_grid(&grid.to_candidate_grid()).unwrap();
+
+        assert_eq!(
+            Solver::all_methods().take_step(&parsed, true),
+            Solver::all_methods().take_step(&grid, true)
+        );
+
+        let mut original = grid;
+        Solver::all_methods()
+            .try_solve(&mut original, false)
+            .unwrap();
+        Solver::all_methods().try_solve(&mut parsed, false).unwrap();
+
+        assert_eq!(parsed, original);
+    }
+
+    #[test]
+    fn candidate_grid_keeps_origins() {
+        let mut grid = mid_solve_grid();
+        grid.set_number(1, 3);
+        let text = grid.to_candidate_grid();
+        let parsed = Grid::from_candidate_grid(&text).unwrap();
+
+        assert!(text.lines().nth(1).unwrap().contains("!3"));
+        assert_eq!(parsed.origin(1), Some(Origin::User));
+        assert_eq!(parsed.origin(6), Some(Origin::Given));
+        assert_eq!(parsed.givens_count(), grid.givens_count());
+
+        let mut grid = mid_solve_grid();
+        grid.place_number(1, 3, Origin::Solver);
+        let parsed = Grid::from_candidate_grid(&grid.to_candidate_grid()).unwrap();
+
+        assert_eq!(parsed, grid);
+        assert_eq!(parsed.origin(1), Some(Origin::Solver));
+        assert_eq!(parsed.origin(6), Some(Origin::Given));
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt::{self, Display, Write};
+
+use crate::cell::Cell;
+
+use super::Grid;
+
+impl Grid {
+    /// Returns prefix of the number in the cell, or space for givens.
+    fn prefix(&self, pos: usize) -> char {
+        self.origin(pos)
+            .and_then(|origin| origin.prefix())
+            .unwrap_or(' ')
+    }
+
+    /// Writes boxed board with numbers only. Numbers, which are not givens,
+    /// are prefixed with [`crate::cell::Origin::prefix`].
+    /// Output can be parsed back by `Grid::from_str`.
+    fn fmt_numbers(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let border = "+-------+-------+-------+";
+
+        for row in 0..9 {
+            if row % 3 == 0 {
+                writeln!(f, "{}", border)?;
+            }
+
+            for col in 0..9 {
+                if col % 3 == 0 && col != 0 {
+                    f.write_str(" ")?;
+                }
+                if col % 3 == 0 {
+                    f.write_str("|")?;
+                }
+
+                let pos = row * 9 + col;
+
+                match &self.matrix[pos] {
+                    Cell::Number(n) => write!(f, "{}{}", self.prefix(pos), n)?,
+                    Cell::Pencilmarks(_) => f.write_str(" .")?,
+                }
+            }
+
+            writeln!(f, " |")?;
+        }
+
+        f.write_str(border)
+    }
+
+    /// Writes boxed board, where every cell is 3x3 mini-grid of pencilmarks.
+    /// Pencilmarks are placed like on the phone keypad (1 2 3 on the top row), missing ones are dots.
+    /// Numbers are written in the center of the mini-grid, prefixed like in [`Grid::fmt_numbers`].
+    fn fmt_pencilmarks(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let border = "+-------------+-------------+-------------+";
+        let spacer = "|             |             |             |";
+
+        for row in 0..9 {
+            if row % 3 == 0 {
+                writeln!(f, "{}", border)?;
+            } else {
+                writeln!(f, "{}", spacer)?;
+            }
+
+            for mini_row in 0..3 {
+                for col in 0..9 {
+                    if col % 3 == 0 {
+                        f.write_str("| ")?;
+                    }
+
+                    for mini_col in 0..3 {
+                        let n = mini_row * 3 + mini_col + 1;
+
+                        let pos = row * 9 + col;
+
+                        let c = match &self.matrix[pos] {
+                            Cell::Number(number) if n == 5 => (b'0' + number) as char,
+                            Cell::Number(_) if n == 4 => self.prefix(pos),
+                            Cell::Number(_) => ' ',
+                            Cell::Pencilmarks(p) if p.contains(&n) => (b'0' + n) as char,
+                            Cell::Pencilmarks(_) => '.',
+                        };
+
+                        f.write_char(c)?;
+                    }
+
+                    f.write_char(' ')?;
+                }
+
+                writeln!(f, "|")?;
+            }
+        }
+
+        f.write_str(border)
+    }
+}
+
+impl Display for Grid {
+    /// `{}` prints numbers only, `{:#}` also prints pencilmarks of every cell.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            self.fmt_pencilmarks(f)
+        } else {
+            self.fmt_numbers(f)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::cell::Origin;
+
+    use super::*;
+
+    #[test]
+    fn display_numbers() {
+        let grid = Grid::from_str(
+            "401003050000605084895400136030060405900050300050001200240500007009000500500092000",
+        )
+        .unwrap();
+
+        assert_eq!(
+            grid.to_string(),
+            "\
++-------+-------+-------+
+| 4 . 1 | . . 3 | . 5 . |
+| . . . | 6 . 5 | . 8 4 |
+| 8 9 5 | 4 . . | 1 3 6 |
++-------+-------+-------+
+| . 3 . | . 6 . | 4 . 5 |
+| 9 . . | . 5 . | 3 . . |
+| . 5 . | . . 1 | 2 . . |
++-------+-------+-------+
+| 2 4 . | 5 . . | . . 7 |
+| . . 9 | . . . | 5 . . |
+| 5 . . | . 9 2 | . . . |
++-------+-------+-------+"
+        );
+
+        assert_eq!(Grid::from_str(&grid.to_string()), Ok(grid));
+    }
+
+    #[test]
+    fn display_pencilmarks() {
+        let grid = Grid::from_str(
+            "401003050000605084895400136030060405900050300050001200240500007009000500500092000",
+        )
+        .unwrap();
+
+        let lines: Vec<String> = format!("{:#}", grid).lines().map(String::from).collect();
+
+        assert_eq!(lines.len(), 9 * 3 + 6 + 4);
+        assert_eq!(lines[0], "+-------------+-------------+-------------+");
+        assert_eq!(lines[1], "|     .2.     | .2. .2.     | ...     .2. |");
+        assert_eq!(lines[2], "|  4  ..6  1  | ... ...  3  | ...  5  ... |");
+        assert_eq!(lines[3], "|     7..     | 789 78.     | 7.9     ..9 |");
+        assert_eq!(lines[4], "|             |             |             |");
+    }
+
+    #[test]
+    fn display_placed_numbers() {
+        let mut grid = Grid::from_str(
+            "401003050000605084895400136030060405900050300050001200240500007009000500500092000",
+        )
+        .unwrap();
+
+        grid.set_number(1, 7);
+
+        let numbers = grid.to_string();
+        let pencilmarks = format!("{:#}", grid);
+
+        assert_eq!(numbers.lines().nth(1), Some("| 4!7 1 | . . 3 | . 5 . |"));
+        assert_eq!(
+            pencilmarks.lines().nth(2),
+            Some("|  4  !7   1  | ... ...  3  | ...  5  ... |")
+        );
+
+        let parsed = Grid::from_str(&numbers).unwrap();
+        assert_eq!(parsed, grid);
+        assert!(!parsed.is_given(1));
+        assert!(parsed.is_given(0));
+    }
+
+    #[test]
+    fn display_round_trip_keeps_origins() {
+        let mut grid = Grid::from_str(
+            "401003050000605084895400136030060405900050300050001200240500007009000500500092000",
+        )
+        .unwrap();
+
+        grid.place_number(1, 7, Origin::User);
+        grid.place_number(3, 2, Origin::Solver);
+
+        let parsed = Grid::from_str(&grid.to_string()).unwrap();
+
+        assert_eq!(parsed, grid);
+
+        for (pos, origin) in [(0, Origin::Given), (1, Origin::User), (3, Origin::Solver)] {
+            assert_eq!(grid.origin(pos), Some(origin));
+            assert_eq!(parsed.origin(pos), Some(origin));
+        }
+
+        assert_eq!(parsed.origin(4), None);
+    }
+}
@@ -0,0 +1,503 @@
+mod als;
+mod candidates;
+mod display;
+mod parse;
+mod validation;
+
+use std::{collections::HashMap, ops::Index};
+
+use crate::{
+    cell::{Cell, Origin},
+    figure::Figure,
+    pencilmarks::Pencilmarks,
+    solver::brute_force::{self, Solutions},
+};
+
+pub use self::{als::Als, parse::ParseGridError, validation::Conflict};
+
+/// Grid represents 9 by 9 matrix of [Cells]
+/// Every number also has its [`Origin`], so that givens can be told apart from solved cells.
+#[derive(Debug, Clone)]
+pub struct Grid {
+    matrix: [Cell; 81],
+    origins: [Option<Origin>; 81],
+    // For every unit (rows, then cols, then sqrs) and number 1-9:
+    // mask, where bit `i` is set if `i`th cell of the unit has the number in pencilmarks.
+    // Is kept in sync with matrix by `write_cell`.
+    unit_positions: [[u16; 9]; 27],
+}
+
+impl Grid {
+    /// Every number of the matrix is given.
+    fn with_givens(matrix: [Cell; 81]) -> Self {
+        let origins = std::array::from_fn(|i| matrix[i].is_number().then_some(Origin::Given));
+
+        let mut grid = Self {
+            matrix: [Cell::Pencilmarks(Pencilmarks::empty()); 81],
+            origins,
+            unit_positions: [[0; 9]; 27],
+        };
+
+        for (i, cell) in matrix.into_iter().enumerate() {
+            grid.write_cell(i, cell);
+        }
+
+        grid
+    }
+
+    /// Returns units (as indices of `unit_positions`) of the cell, with index of the cell in each unit.
+    fn units_of(i: usize) -> [(usize, usize); 3] {
+        let row = i / 9;
+        let col = i % 9;
+        let sqr = (row / 3) * 3 + col / 3;
+
+        [
+            (row, col),
+            (9 + col, row),
+            (18 + sqr, (row % 3) * 3 + col % 3),
+        ]
+    }
+
+    /// Returns position of `index`th cell of the unit.
+    fn cell_of_unit(unit: usize, index: usize) -> usize {
+        match unit {
+            0..=8 => unit * 9 + index,
+            9..=17 => index * 9 + (unit - 9),
+            _ => {
+                let sqr = unit - 18;
+                ((sqr / 3) * 3 + index / 3) * 9 + (sqr % 3) * 3 + index % 3
+            }
+        }
+    }
+
+    /// Returns index of the unit in `unit_positions`, if figure is exactly one row, col or sqr.
+    fn unit_of_figure(figure: &Figure) -> Option<usize> {
+        if figure.len() != 9 {
+            return None;
+        }
+
+        figure
+            .is_on_the_same_row()
+            .map(|row| row as usize)
+            .or_else(|| figure.is_on_the_same_col().map(|col| 9 + col as usize))
+            .or_else(|| figure.is_on_the_same_sqr().map(|sqr| 18 + sqr as usize))
+    }
+
+    /// The only way to change the matrix: keeps `unit_positions` up to date.
+    fn write_cell(&mut self, i: usize, cell: Cell) {
+        let old = self.matrix[i].pencilmarks_mask().mask();
+        let new = cell.pencilmarks_mask().mask();
+        let changed = old ^ new;
+
+        if changed != 0 {
+            for (unit, index) in Self::units_of(i) {
+                for number in Pencilmarks::from_mask(changed) {
+                    self.unit_positions[unit][number as usize - 1] ^= 1 << index;
+                }
+            }
+        }
+
+        self.matrix[i] = cell;
+    }
+
+    fn updtae_cell_neighbours(&mut self, i: usize) {
+        if let Cell::Number(n) = self.matrix[i] {
+            for neighbour_pos in Figure::neighbours(i) {
+                if let Cell::Pencilmarks(mut p) = self.matrix[neighbour_pos] {
+                    if p.contains(&n) {
+                        p.remove(n);
+                        self.write_cell(neighbour_pos, Cell::Pencilmarks(p));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Places number, entered by hand. Same as [`Grid::place_number`] with [`Origin::User`].
+    pub fn set_number(&mut self, position: usize, number: u8) {
+        self.place_number(position, number, Origin::User);
+    }
+
+    /// Places number and removes it from pencilmarks of the neighbours.
+    pub fn place_number(&mut self, position: usize, number: u8, origin: Origin) {
+        self.write_cell(position, Cell::Number(number));
+        self.origins[position] = Some(origin);
+        self.updtae_cell_neighbours(position);
+    }
+
+    /// Numbers outside of 1-9 are skipped, repeated numbers are kept once.
+    pub fn set_pencilmarks(&mut self, position: usize, pencilmarks: Vec<u8>) {
+        let pencilmarks = pencilmarks
+            .into_iter()
+            .filter(|number| (1..=9).contains(number))
+            .collect();
+
+        self.write_cell(position, Cell::Pencilmarks(pencilmarks));
+        self.origins[position] = None;
+    }
+
+    /// Returns who placed the number at the given position.
+    /// Returns `None` if there is no number.
+    pub fn origin(&self, position: usize) -> Option<Origin> {
+        self.origins[position]
+    }
+
+    /// Returns `true` if there is a given number at the position.
+    pub fn is_given(&self, position: usize) -> bool {
+        self.origins[position] == Some(Origin::Given)
+    }
+
+    /// Returns the number of givens (clues) in the grid.
+    pub fn givens_count(&self) -> usize {
+        self.origins
+            .iter()
+            .filter(|origin| **origin == Some(Origin::Given))
+            .count()
+    }
+
+    /// Returns map: number to cell in which it occurs.
+    /// Same as [`Grid::pencilmarks_positions`], but allocates a map of vectors.
+    pub fn pencilmarks_info(&self, figure: Figure) -> HashMap<u8, Vec<usize>> {
+        self.pencilmarks_positions(figure)
+            .into_iter()
+            .map(|(number, positions)| (number, positions.iter().collect()))
+            .collect()
+    }
+
+    /// Returns positions of every number in pencilmarks of the figure.
+    /// For rows, cols and sqrs uses precomputed positions, so it is cheap.
+    pub fn pencilmarks_positions(&self, figure: Figure) -> PencilmarksInfo {
+        let Some(unit) = Self::unit_of_figure(&figure) else {
+            return self.scan_pencilmarks_positions(figure);
+        };
+
+        let mut res = PencilmarksInfo::default();
+
+        for (number, &positions) in (1..=9).zip(self.unit_positions[unit].iter()) {
+            for index in Pencilmarks::from_mask(positions << 1) {
+                res.insert(number, Self::cell_of_unit(unit, index as usize - 1));
+            }
+        }
+
+        res
+    }
+
+    /// Same as [`Grid::pencilmarks_positions`], but looks through every cell of the figure.
+    fn scan_pencilmarks_positions(&self, figure: Figure) -> PencilmarksInfo {
+        let mut res = PencilmarksInfo::default();
+
+        for i in figure {
+            for pencilmark in self[i].pencilmarks_mask() {
+                res.insert(pencilmark, i);
+            }
+        }
+
+        res
+    }
+
+    /// Returns `true` if every cell in grid is `Number`
+    pub fn is_solved(&self) -> bool {
+        !self.matrix.iter().any(|cell| cell.is_pencilmarks())
+    }
+
+    /// Counts solutions of the grid, stopping after `limit` solutions found.
+    /// Uses [`brute_force`], so pencilmarks of the grid are respected.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        brute_force::count_solutions(self, limit)
+    }
+
+    /// Returns `true` if grid has exactly one solution.
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    /// Returns [`Solutions::Unique`] with the solution if grid has exactly one solution.
+    /// Otherwise, returns [`Solutions::NoSolution`] or two of the solutions in [`Solutions::Multiple`].
+    pub fn solutions(&self) -> Solutions {
+        brute_force::solutions(self)
+    }
+}
+
+/// Positions of every number in the figure, returned by [`Grid::pencilmarks_positions`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PencilmarksInfo {
+    // Positions of number `n` are at index `n - 1`.
+    positions: [Figure; 9],
+}
+
+impl PencilmarksInfo {
+    fn insert(&mut self, number: u8, position: usize) {
+        self.positions[number as usize - 1].insert(position);
+    }
+
+    /// Returns positions of the number, or `None` if no cell of the figure has it.
+    pub fn get(&self, number: u8) -> Option<&Figure> {
+        let index = (number as usize).checked_sub(1)?;
+
+        self.positions
+            .get(index)
+            .filter(|positions| !positions.is_empty())
+    }
+
+    /// Returns numbers (in ascending order) with their positions.
+    /// Numbers without positions are skipped.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &Figure)> {
+        (1..=9)
+            .zip(self.positions.iter())
+            .filter(|(_, positions)| !positions.is_empty())
+    }
+}
+
+impl IntoIterator for PencilmarksInfo {
+    type Item = (u8, Figure);
+
+    type IntoIter = std::iter::Filter<
+        std::iter::Zip<std::ops::RangeInclusive<u8>, std::array::IntoIter<Figure, 9>>,
+        fn(&(u8, Figure)) -> bool,
+    >;
+
+    /// Same as [`PencilmarksInfo::iter`], but takes ownership.
+    fn into_iter(self) -> Self::IntoIter {
+        fn has_positions((_, positions): &(u8, Figure)) -> bool {
+            !positions.is_empty()
+        }
+
+        (1..=9)
+            .zip(self.positions)
+            .filter(has_positions as fn(&(u8, Figure)) -> bool)
+    }
+}
+
+/// Grids are equal if their cells are equal. Origins of the numbers are not compared.
+impl PartialEq for Grid {
+    fn eq(&self, other: &Self) -> bool {
+        self.matrix == other.matrix
+    }
+}
+
+impl Index<usize> for Grid {
+    type Output = Cell;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.matrix[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn set_number() {
+        let mut grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        grid.set_number(40, 5);
+
+        let grid_should_be = Grid::from_str(
+            //                                       * here is this 5
+            "000004028406000005100030600000301000087050140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        assert_eq!(grid, grid_should_be);
+    }
+
+    #[test]
+    fn set_pencilmarks() {
+        let mut grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        let mut grid_should_be = grid.clone();
+
+        grid.set_pencilmarks(40, vec![2, 5]);
+
+        grid_should_be.matrix[40] = Cell::Pencilmarks(vec![2, 5].into());
+
+        assert_eq!(grid, grid_should_be);
+    }
+    #[test]
+    fn update_cell_neighbours() {
+        let mut grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        // Set manually, so that neighbours do not update
+        grid.write_cell(40, Cell::Number(5));
+
+        let mut grid_should_be = grid.clone();
+
+        // Column
+        grid_should_be.matrix[4] = Cell::Pencilmarks(vec![6, 7, 9].into());
+        grid_should_be.matrix[13] = Cell::Pencilmarks(vec![2, 7, 8, 9].into());
+        grid_should_be.matrix[31] = Cell::Pencilmarks(vec![2, 4, 6, 8].into());
+        grid_should_be.matrix[49] = Cell::Pencilmarks(vec![2, 4, 6, 8].into());
+        grid_should_be.matrix[67] = Cell::Pencilmarks(vec![2, 6, 8].into());
+        grid_should_be.matrix[76] = Cell::Pencilmarks(vec![2, 8, 9].into());
+        // Row
+        grid_should_be.matrix[36] = Cell::Pencilmarks(vec![2, 3].into());
+        grid_should_be.matrix[39] = Cell::Pencilmarks(vec![2, 6].into());
+        grid_should_be.matrix[41] = Cell::Pencilmarks(vec![2, 6].into());
+        grid_should_be.matrix[44] = Cell::Pencilmarks(vec![2, 6, 9].into());
+        // Square (31, 39, 41, 49) - already done
+
+        grid.updtae_cell_neighbours(40);
+
+        assert_eq!(grid, grid_should_be);
+    }
+
+    #[test]
+    fn pencilmarks_info() {
+        let grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        let info = grid.pencilmarks_info(Figure::row(0));
+
+        assert_eq!(
+            info,
+            HashMap::from([
+                (1, vec![3]),
+                (3, vec![0, 1, 2, 6]),
+                (5, vec![0, 1, 2, 3, 4]),
+                (6, vec![3, 4]),
+                (7, vec![0, 4, 6]),
+                (9, vec![1, 2, 3, 4, 6]),
+            ])
+        );
+    }
+
+    #[test]
+    fn pencilmarks_positions() {
+        let grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        let info = grid.pencilmarks_positions(Figure::row(0));
+
+        assert_eq!(
+            info.iter()
+                .map(|(number, positions)| (number, positions.iter().collect()))
+                .collect::<Vec<(u8, Vec<usize>)>>(),
+            vec![
+                (1, vec![3]),
+                (3, vec![0, 1, 2, 6]),
+                (5, vec![0, 1, 2, 3, 4]),
+                (6, vec![3, 4]),
+                (7, vec![0, 4, 6]),
+                (9, vec![1, 2, 3, 4, 6]),
+            ]
+        );
+        assert_eq!(info.get(6), Some(&vec![3, 4].into()));
+        assert_eq!(info.get(2), None);
+        assert_eq!(info.get(0), None);
+    }
+
+    #[test]
+    fn pencilmarks_positions_are_kept_in_sync() {
+        let mut grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        grid.set_number(0, 7);
+        grid.set_pencilmarks(1, vec![3, 9]);
+        grid.set_pencilmarks(0, vec![3, 5, 7]);
+        grid.place_number(40, 5, Origin::Solver);
+
+        for figure in Figure::all_figures() {
+            assert_eq!(
+                grid.pencilmarks_positions(figure.clone()),
+                grid.scan_pencilmarks_positions(figure)
+            );
+        }
+    }
+
+    #[test]
+    fn set_pencilmarks_out_of_range() {
+        let mut grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        grid.set_pencilmarks(0, vec![0, 3, 12, 3, 1]);
+
+        assert_eq!(grid[0], Cell::Pencilmarks(vec![1, 3].into()));
+        assert_eq!(grid[0].pencilmarks(), vec![1, 3]);
+        assert_eq!(
+            grid.pencilmarks_positions(Figure::row(0)).get(3),
+            Some(&vec![0, 1, 2, 6].into())
+        );
+    }
+
+    #[test]
+    fn is_solved() {
+        let unsolved_grid = Grid::from_str(
+            "007010000000800500180009064600000003071080640400000005840600031005002000000030700",
+        )
+        .unwrap();
+        let solved_grid = Grid::from_str(
+            "567314928394826517182759364658247193971583642423961875849675231735192486216438759",
+        )
+        .unwrap();
+
+        assert!(!unsolved_grid.is_solved());
+        assert!(solved_grid.is_solved());
+    }
+
+    #[test]
+    fn unique_solution() {
+        let unique = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+        let empty = Grid::from_str(&"0".repeat(81)).unwrap();
+
+        assert!(unique.has_unique_solution());
+        assert!(!empty.has_unique_solution());
+        assert_eq!(empty.count_solutions(5), 5);
+        assert!(matches!(unique.solutions(), Solutions::Unique(_)));
+        assert!(matches!(empty.solutions(), Solutions::Multiple(_, _)));
+    }
+
+    #[test]
+    fn origins() {
+        let mut grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        assert_eq!(grid.origin(5), Some(Origin::Given));
+        assert_eq!(grid.origin(0), None);
+        assert_eq!(grid.givens_count(), 26);
+
+        grid.set_number(0, 7);
+        grid.place_number(1, 3, Origin::Solver);
+
+        assert_eq!(grid.origin(0), Some(Origin::User));
+        assert_eq!(grid.origin(1), Some(Origin::Solver));
+        assert!(!grid.is_given(0));
+        assert_eq!(grid.givens_count(), 26);
+
+        grid.set_pencilmarks(0, vec![7]);
+        assert_eq!(grid.origin(0), None);
+
+        let solution = grid.solutions();
+        let Solutions::Unique(solution) = solution else {
+            panic!("grid should have unique solution");
+        };
+
+        assert_eq!(solution.origin(0), Some(Origin::Solver));
+        assert_eq!(solution.origin(5), Some(Origin::Given));
+        assert_eq!(solution.givens_count(), 26);
+    }
+}
@@ -0,0 +1,302 @@
+use std::{array, str::FromStr};
+
+use crate::{
+    cell::{Cell, Origin},
+    figure::Figure,
+};
+
+use super::Grid;
+
+/// Characters, meaning empty cell.
+const EMPTY_CELLS: [char; 4] = ['0', '.', '_', '*'];
+/// Characters, which are ignored, so that grid can be drawn with borders.
+const DECORATIONS: [char; 4] = ['|', '-', '+', '='];
+
+impl FromStr for Grid {
+    type Err = ParseGridError;
+
+    /// Supports one-line Sudoku and multi-line layouts.
+    /// Cell is either digit 1-9 or empty: `0`, `.`, `_` or `*`.
+    /// Whitespace and separators (`|`, `-`, `+`, `=`) are ignored.
+    /// Every other character returns InvalidCharacter error.
+    ///
+    /// Digits are givens, unless prefixed with [`Origin::prefix`]: `~` for numbers,
+    /// placed by the solver, and `!` for numbers, placed by hand.
+    ///
+    /// ```
+    /// # use std::str::FromStr;
+    /// # use human_sudoku_solver::grid::Grid;
+    /// let one_line = Grid::from_str(
+    ///     "4.1..3.5....6.5.848954..136.3..6.4.59...5.3...5...12..24.5....7..9...5..5...92...",
+    /// );
+    /// let multi_line = Grid::from_str(
+    ///     "4 . 1 | . . 3 | . 5 .
+    ///      . . . | 6 . 5 | . 8 4
+    ///      8 9 5 | 4 . . | 1 3 6
+    ///      ------+-------+------
+    ///      . 3 . | . 6 . | 4 . 5
+    ///      9 . . | . 5 . | 3 . .
+    ///      . 5 . | . . 1 | 2 . .
+    ///      ------+-------+------
+    ///      2 4 . | 5 . . | . . 7
+    ///      . . 9 | . . . | 5 . .
+    ///      5 . . | . 9 2 | . . .",
+    /// );
+    /// assert_eq!(one_line, multi_line);
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Number and its origin, if it is prefixed
+        let mut numbers = vec![];
+
+        for (line, line_str) in s.lines().enumerate() {
+            let mut chars = line_str.chars().enumerate().peekable();
+
+            while let Some((column, c)) = chars.next() {
+                let invalid = ParseGridError::InvalidCharacter {
+                    line: line + 1,
+                    column: column + 1,
+                };
+
+                if c.is_whitespace() || DECORATIONS.contains(&c) {
+                    continue;
+                }
+
+                if EMPTY_CELLS.contains(&c) {
+                    numbers.push((0, None));
+                } else if let Some(digit) = c.to_digit(10) {
+                    numbers.push((digit as u8, None));
+                } else if let Some(origin) = Origin::from_prefix(c) {
+                    // Prefix is honoured only directly before a number
+                    match chars.next_if(|(_, next)| matches!(next, '1'..='9')) {
+                        Some((_, digit)) => numbers.push((digit as u8 - b'0', Some(origin))),
+                        None => return Err(invalid),
+                    }
+                } else {
+                    return Err(invalid);
+                }
+            }
+        }
+
+        if numbers.len() != 9 * 9 {
+            return Err(ParseGridError::InvalidSize(numbers.len()));
+        }
+
+        let mut matrix = array::from_fn(|_| Cell::all_pencilmarks());
+
+        for (i, (number, _)) in numbers.iter().enumerate() {
+            if *number != 0 {
+                matrix[i] = Cell::Number(*number);
+            }
+        }
+
+        let mut grid = Self::with_givens(matrix);
+
+        for (i, (_, origin)) in numbers.into_iter().enumerate() {
+            if origin.is_some() {
+                grid.origins[i] = origin;
+            }
+        }
+
+        for cell in Figure::all_cells() {
+            grid.updtae_cell_neighbours(cell);
+        }
+
+        Ok(grid)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// Contains the number of cells found.
+    InvalidSize(usize),
+    /// Line and column are counted from 1.
+    InvalidCharacter { line: usize, column: usize },
+}
+
+impl std::fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            ParseGridError::InvalidSize(size) => write!(f, "Invalid Sudoku grid size: {}", size),
+            ParseGridError::InvalidCharacter { line, column } => {
+                write!(f, "Invalid character at line {}, column {}", line, column)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn init_string_size_is_too_small() {
+        let grid = Grid::from_str(
+            "40100305000060508489540013603006040590005030005000120024050000700900050050009200",
+        );
+        assert_eq!(grid, Err(ParseGridError::InvalidSize(80)));
+    }
+
+    #[test]
+    fn init_string_size_is_too_big() {
+        let grid = Grid::from_str(
+            "4010030500006050848954001360300604059000503000500012002405000070090005005000920001234",
+        );
+        assert_eq!(grid, Err(ParseGridError::InvalidSize(85)));
+    }
+
+    #[test]
+    fn init_string_chars_are_invalid() {
+        let grid = Grid::from_str(
+            "4x1003050000605084895400136030060405900050300050001200240500007009000500500092000",
+        );
+        assert_eq!(
+            grid,
+            Err(ParseGridError::InvalidCharacter { line: 1, column: 2 })
+        );
+
+        let grid = Grid::from_str(
+            "401 003 050
+             000 605 084
+             895 400 13a",
+        );
+        assert_eq!(
+            grid,
+            Err(ParseGridError::InvalidCharacter {
+                line: 3,
+                column: 24
+            })
+        );
+    }
+
+    #[test]
+    fn init_string_whitespace_is_ignored() {
+        let grid = Grid::from_str(
+            "4 1  3 5    6 5 848954  136 3  6 4 59   5 3   5   120 24 5    7  9   5  5   92   ",
+        );
+        assert_eq!(grid, Err(ParseGridError::InvalidSize(35)));
+    }
+
+    #[test]
+    fn init_string_is_ok() {
+        let grid = Grid::from_str(
+            "401003050000605084895400136030060405900050300050001200240500007009000500500092000",
+        )
+        .unwrap();
+
+        assert_eq!(grid.matrix[8], Cell::Pencilmarks(vec![2, 9].into()));
+        assert_eq!(
+            grid.matrix[38],
+            Cell::Pencilmarks(vec![2, 4, 6, 7, 8].into())
+        );
+        assert_eq!(grid.matrix[80], Cell::Pencilmarks(vec![1, 3, 8].into()));
+
+        assert_eq!(grid.matrix[0], Cell::Number(4));
+        assert_eq!(grid.matrix[2], Cell::Number(1));
+        assert_eq!(grid.matrix[26], Cell::Number(6));
+        assert_eq!(grid.matrix[36], Cell::Number(9));
+    }
+
+    #[test]
+    fn init_string_with_dots() {
+        let zeros = Grid::from_str(
+            "401003050000605084895400136030060405900050300050001200240500007009000500500092000",
+        );
+        let dots = Grid::from_str(
+            "4.1..3.5....6.5.848954..136.3..6.4.59...5.3...5...12..24_5****7..9...5..5...92...",
+        );
+
+        assert_eq!(zeros, dots);
+    }
+
+    #[test]
+    fn init_string_multi_line() {
+        let one_line = Grid::from_str(
+            "401003050000605084895400136030060405900050300050001200240500007009000500500092000",
+        );
+        let multi_line = Grid::from_str(
+            "
+            +-------+-------+-------+
+            | 4 . 1 | . . 3 | . 5 . |
+            | . . . | 6 . 5 | . 8 4 |
+            | 8 9 5 | 4 . . | 1 3 6 |
+            +-------+-------+-------+
+            | . 3 . | . 6 . | 4 . 5 |
+            | 9 . . | . 5 . | 3 . . |
+            | . 5 . | . . 1 | 2 . . |
+            +=======+=======+=======+
+            | 2 4 . | 5 . . | . . 7 |
+            | . . 9 | . . . | 5 . . |
+            | 5 . . | . 9 2 | . . . |
+            +-------+-------+-------+
+            ",
+        );
+
+        assert_eq!(one_line, multi_line);
+    }
+
+    #[test]
+    fn init_string_with_placed_numbers() {
+        let grid = Grid::from_str(
+            "4!71~203050000605084895400136030060405900050300050001200240500007009000500500092000",
+        )
+        .unwrap();
+
+        assert_eq!(grid.origin(0), Some(Origin::Given));
+        assert_eq!(grid.origin(1), Some(Origin::User));
+        assert_eq!(grid.origin(3), Some(Origin::Solver));
+        assert_eq!(grid.origin(4), None);
+        assert_eq!(grid.givens_count(), 34);
+
+        let multi_line = Grid::from_str(
+            "
+            +-------+-------+-------+
+            | 4!7 1 | . . 3 | . 5 . |
+            | . . . | 6 . 5 | . 8 4 |
+            | 8 9 5 | 4 . . | 1 3 6 |
+            +-------+-------+-------+
+            | . 3 . | . 6 . | 4 . 5 |
+            | 9 . . | . 5 . | 3 . . |
+            | . 5 . | . . 1 | 2 . . |
+            +-------+-------+-------+
+            | 2 4 . | 5 . . | . . 7 |
+            | . . 9 | . . . | 5 . . |
+            | 5 . . | . 9 2 | . . . |
+            +-------+-------+-------+
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(multi_line.origin(1), Some(Origin::User));
+        assert_eq!(multi_line.givens_count(), 34);
+    }
+
+    #[test]
+    fn init_string_plus_is_decoration() {
+        let one_line = Grid::from_str(
+            "401003050000605084895400136030060405900050300050001200240500007009000500500092000",
+        );
+        let with_plus = Grid::from_str(
+            "401+003+050000+605+084895+400+136030060405900050300050001200240500007009000500500092000",
+        )
+        .unwrap();
+
+        assert_eq!(one_line, Ok(with_plus.clone()));
+        assert_eq!(with_plus.givens_count(), 34);
+    }
+
+    #[test]
+    fn init_string_prefix_without_number() {
+        assert_eq!(
+            Grid::from_str(
+                "4!01003050000605084895400136030060405900050300050001200240500007009000500500092000"
+            ),
+            Err(ParseGridError::InvalidCharacter { line: 1, column: 2 })
+        );
+        assert_eq!(
+            Grid::from_str("4 ~ 1"),
+            Err(ParseGridError::InvalidCharacter { line: 1, column: 3 })
+        );
+    }
+}
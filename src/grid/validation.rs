@@ -0,0 +1,175 @@
+use std::fmt::Display;
+
+use crate::{cell::Cell, figure::Figure, pencilmarks::Pencilmarks};
+
+use super::Grid;
+
+/// Reason, why the grid can't be solved.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum Conflict {
+    /// Same number is placed in several cells of the unit.
+    DuplicateNumber {
+        unit: Figure,
+        cells: Figure,
+        number: u8,
+    },
+    /// Cell has no pencilmarks left.
+    NoPencilmarks { cell: usize },
+    /// Number is not placed in the unit, and no cell of the unit has it in pencilmarks.
+    NoPlaceForNumber { unit: Figure, number: u8 },
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Conflict::DuplicateNumber { cells, number, .. } => {
+                write!(
+                    f,
+                    "Number {} is placed in cells {:?}",
+                    number,
+                    cells.clone().into_iter().collect::<Vec<_>>()
+                )
+            }
+            Conflict::NoPencilmarks { cell } => write!(f, "Cell {} has no pencilmarks", cell),
+            Conflict::NoPlaceForNumber { unit, number } => {
+                write!(
+                    f,
+                    "Number {} has no place in cells {:?}",
+                    number,
+                    unit.clone().into_iter().collect::<Vec<_>>()
+                )
+            }
+        }
+    }
+}
+
+impl Grid {
+    /// Checks that the grid has no obvious contradictions:
+    /// duplicate numbers in the unit, cells without pencilmarks, numbers without place in the unit.
+    /// Units are checked in [`Figure::all_figures`] order, then cells.
+    /// Grid without conflicts still might have no solution.
+    pub fn validate(&self) -> Result<(), Vec<Conflict>> {
+        let mut conflicts = vec![];
+
+        for unit in Figure::all_figures() {
+            for number in 1..=9 {
+                let cells: Vec<usize> = unit
+                    .clone()
+                    .into_iter()
+                    .filter(|&pos| self.matrix[pos] == Cell::Number(number))
+                    .collect();
+
+                if cells.len() > 1 {
+                    conflicts.push(Conflict::DuplicateNumber {
+                        unit: unit.clone(),
+                        cells: cells.into(),
+                        number,
+                    });
+                } else if cells.is_empty()
+                    && !unit.clone().into_iter().any(|pos| match &self.matrix[pos] {
+                        Cell::Pencilmarks(p) => p.contains(&number),
+                        Cell::Number(_) => false,
+                    })
+                {
+                    conflicts.push(Conflict::NoPlaceForNumber {
+                        unit: unit.clone(),
+                        number,
+                    });
+                }
+            }
+        }
+
+        for cell in Figure::all_cells() {
+            if self.matrix[cell] == Cell::Pencilmarks(Pencilmarks::empty()) {
+                conflicts.push(Conflict::NoPencilmarks { cell });
+            }
+        }
+
+        if conflicts.is_empty() {
+            Ok(())
+        } else {
+            Err(conflicts)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn valid() {
+        let grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        assert_eq!(grid.validate(), Ok(()));
+    }
+
+    #[test]
+    fn duplicate_number() {
+        let grid = Grid::from_str(
+            "600004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        // 6 is in the first column and square twice, so there is no place left for 7 there.
+        // 6 also has no place in the second square, since it is already in its rows and cols.
+        assert_eq!(
+            grid.validate(),
+            Err(vec![
+                Conflict::DuplicateNumber {
+                    unit: Figure::col(0),
+                    cells: vec![0, 72].into(),
+                    number: 6,
+                },
+                Conflict::NoPlaceForNumber {
+                    unit: Figure::col(0),
+                    number: 7,
+                },
+                Conflict::DuplicateNumber {
+                    unit: Figure::sqr(0),
+                    cells: vec![0, 11].into(),
+                    number: 6,
+                },
+                Conflict::NoPlaceForNumber {
+                    unit: Figure::sqr(0),
+                    number: 7,
+                },
+                Conflict::NoPlaceForNumber {
+                    unit: Figure::sqr(1),
+                    number: 6,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn no_pencilmarks_and_no_place() {
+        let mut grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        // The only place for 7 in the first column and square
+        grid.set_pencilmarks(0, vec![]);
+
+        assert_eq!(
+            grid.validate(),
+            Err(vec![
+                Conflict::NoPlaceForNumber {
+                    unit: Figure::col(0),
+                    number: 7,
+                },
+                Conflict::NoPlaceForNumber {
+                    unit: Figure::sqr(0),
+                    number: 7,
+                },
+                Conflict::NoPencilmarks { cell: 0 },
+            ])
+        );
+    }
+}
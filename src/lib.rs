@@ -2,6 +2,9 @@
 pub mod action;
 pub mod cell;
 pub mod figure;
+pub mod generator;
+pub mod grading;
 pub mod grid;
 pub mod methods;
+pub mod pencilmarks;
 pub mod solver;
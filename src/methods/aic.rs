@@ -0,0 +1,397 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    fmt::Display,
+};
+
+use itertools::Itertools;
+
+use crate::{action::Action, cell::Cell, figure::Figure, grid::Grid};
+
+use super::Method;
+
+/// Number in one cell, or grouped node: number in several cells of one sqr on one line
+/// (true if the number is in any of them).
+#[derive(Clone, PartialEq, Debug)]
+struct Node {
+    number: u8,
+    cells: Figure,
+}
+
+impl Node {
+    fn single(&self) -> Option<usize> {
+        (self.cells.len() == 1).then(|| self.cells.first().unwrap())
+    }
+}
+
+/// Nodes of the grid and links between them (by indexes in `nodes`).
+struct Links {
+    nodes: Vec<Node>,
+    /// If one node is false, the other is true.
+    strong: Vec<Vec<usize>>,
+    /// If one node is true, the other is false.
+    weak: Vec<BTreeSet<usize>>,
+}
+
+impl Links {
+    fn new(grid: &Grid) -> Self {
+        let mut nodes = vec![];
+
+        for pos in Figure::all_cells() {
+            if let Cell::Pencilmarks(pencilmarks) = grid[pos] {
+                for number in pencilmarks {
+                    nodes.push(Node {
+                        number,
+                        cells: vec![pos].into(),
+                    });
+                }
+            }
+        }
+
+        for sqr in 0..9 {
+            let lines = (0..9).flat_map(|i| [Figure::row(i), Figure::col(i)]);
+
+            for line in lines {
+                let segment = Figure::sqr(sqr).intersection(line);
+
+                for (number, positions) in grid.pencilmarks_positions(segment) {
+                    if positions.len() >= 2 {
+                        nodes.push(Node {
+                            number,
+                            cells: positions,
+                        });
+                    }
+                }
+            }
+        }
+
+        let mut strong = vec![vec![]; nodes.len()];
+        let mut weak = vec![BTreeSet::new(); nodes.len()];
+
+        // Number in one unit
+        for unit in Figure::all_figures() {
+            for (number, positions) in grid.pencilmarks_positions(unit.clone()) {
+                let in_unit: Vec<usize> = (0..nodes.len())
+                    .filter(|&i| {
+                        nodes[i].number == number
+                            && nodes[i].cells.difference(unit.clone()).is_empty()
+                    })
+                    .collect();
+
+                for &a in &in_unit {
+                    for &b in &in_unit {
+                        if nodes[a]
+                            .cells
+                            .intersection(nodes[b].cells.clone())
+                            .is_empty()
+                        {
+                            weak[a].insert(b);
+
+                            if nodes[a].cells.union(nodes[b].cells.clone()) == positions
+                                && !strong[a].contains(&b)
+                            {
+                                strong[a].push(b);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Numbers in one cell
+        for a in 0..nodes.len() {
+            let Some(pos) = nodes[a].single() else {
+                continue;
+            };
+
+            for (b, node) in nodes.iter().enumerate() {
+                if a != b && node.single() == Some(pos) {
+                    weak[a].insert(b);
+
+                    if grid[pos].pencilmarks_mask().len() == 2 {
+                        strong[a].push(b);
+                    }
+                }
+            }
+        }
+
+        Self {
+            nodes,
+            strong,
+            weak,
+        }
+    }
+
+    /// Single nodes, which are weakly linked to both given nodes, except `excluded`.
+    fn seen_by_both(&self, a: usize, b: usize, excluded: &[usize]) -> Vec<usize> {
+        self.weak[a]
+            .intersection(&self.weak[b])
+            .filter(|i| !excluded.contains(i) && self.nodes[**i].single().is_some())
+            .copied()
+            .collect()
+    }
+}
+
+/// Alternating Inference Chain: nodes, linked by strong and weak links in turn,
+/// starting and ending with strong link. Either the first or the last node is true,
+/// so every candidate, weakly linked to both of them, can be removed.
+///
+/// Nice loops are found too:
+/// - Continuous loop (the last node is weakly linked to the first): every link of the loop
+///   has exactly one true node, so every candidate, linked to both nodes of any link, can be removed.
+/// - Discontinuous loop with two strong links at the first node: it is true.
+///
+/// Chains are searched on (cell, number) nodes and grouped nodes.
+#[derive(Clone, Debug)]
+pub struct Aic {
+    max_length: usize,
+}
+
+impl Default for Aic {
+    fn default() -> Self {
+        Self { max_length: 10 }
+    }
+}
+
+impl Aic {
+    /// Returns AIC, which searches chains of up to 10 links.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum number of links in the chain.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// Follows chains, which start with strong link from `start` node.
+    /// State is node index * 2 + 1, if the node is true, or node index * 2, if it is false.
+    /// Returns parent of every reached state.
+    fn search(&self, links: &Links, start: usize) -> Vec<Option<usize>> {
+        let mut parents = vec![None; links.nodes.len() * 2];
+        let mut queue = VecDeque::from([(start * 2, 0)]);
+
+        while let Some((state, length)) = queue.pop_front() {
+            if length == self.max_length {
+                continue;
+            }
+
+            let (node, is_true) = (state / 2, state % 2 == 1);
+
+            let next: Vec<usize> = if is_true {
+                links.weak[node].iter().map(|&i| i * 2).collect()
+            } else {
+                links.strong[node].iter().map(|&i| i * 2 + 1).collect()
+            };
+
+            for next in next {
+                if next != start * 2 && parents[next].is_none() {
+                    parents[next] = Some(state);
+                    queue.push_back((next, length + 1));
+                }
+            }
+        }
+
+        parents
+    }
+
+    /// Returns nodes of the chain from `start` to `end` (which is true).
+    fn chain(parents: &[Option<usize>], start: usize, end: usize) -> Vec<usize> {
+        let mut res = vec![end];
+        let mut state = end * 2 + 1;
+
+        while let Some(parent) = parents[state] {
+            res.push(parent / 2);
+            state = parent;
+        }
+
+        debug_assert_eq!(*res.last().unwrap(), start);
+
+        res.reverse();
+        res
+    }
+
+    fn removal(links: &Links, node: usize) -> Action {
+        Action::RemovePencilmarks {
+            figure: links.nodes[node].cells.clone(),
+            pencilmarks: vec![links.nodes[node].number],
+        }
+    }
+}
+
+impl Display for Aic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Alternating Inference Chain")
+    }
+}
+
+impl Method for Aic {
+    fn get_all_applications(&self, grid: &Grid) -> BTreeSet<Action> {
+        let mut res = BTreeSet::new();
+        let links = Links::new(grid);
+
+        for start in 0..links.nodes.len() {
+            let parents = self.search(&links, start);
+
+            for end in 0..links.nodes.len() {
+                if parents[end * 2 + 1].is_none() {
+                    continue;
+                }
+
+                if end == start {
+                    // Discontinuous loop
+                    if let Some(position) = links.nodes[start].single() {
+                        res.insert(Action::PlaceNumber {
+                            position,
+                            number: links.nodes[start].number,
+                        });
+                    }
+                    continue;
+                }
+
+                res.extend(
+                    links
+                        .seen_by_both(start, end, &[])
+                        .into_iter()
+                        .map(|node| Self::removal(&links, node)),
+                );
+
+                if !links.weak[end].contains(&start) {
+                    continue;
+                }
+
+                // Continuous loop
+                let chain = Self::chain(&parents, start, end);
+
+                if chain.len() < 4 || !chain.iter().all_unique() {
+                    continue;
+                }
+
+                for (i, &a) in chain.iter().enumerate() {
+                    let b = chain[(i + 1) % chain.len()];
+
+                    res.extend(
+                        links
+                            .seen_by_both(a, b, &chain)
+                            .into_iter()
+                            .map(|node| Self::removal(&links, node)),
+                    );
+                }
+            }
+        }
+
+        res
+    }
+
+    fn difficulty(&self) -> u32 {
+        400
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::methods::test_helpful_method;
+
+    use super::*;
+
+    #[test]
+    fn aic() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .-------------------.----------------.------------------.
+            | 2568   356   2458 | 2789  348  678 | 2379  579   1    |
+            | 9      34    7    | 24    5    1   | 23    68    68   |
+            | 12568  1356  258  | 2789  38   678 | 2379  4     579  |
+            :-------------------+----------------+------------------:
+            | 3      7     1    | 58    6    4   | 89    2     589  |
+            | 4      8     6    | 57    9    2   | 17    157   3    |
+            | 25     9     25   | 3     1    78  | 4678  678   4678 |
+            :-------------------+----------------+------------------:
+            | 68     2     3    | 1     7    5   | 4689  689   4689 |
+            | 168    146   9    | 468   2    3   | 5     1678  678  |
+            | 7      156   458  | 68    48   9   | 168   3     2    |
+            '-------------------'----------------'------------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            Aic::new(),
+            [
+                (0, 5),
+                (2, 5),
+                (19, 3),
+                // Needs grouped node
+                (19, 5),
+                (53, 7),
+                (73, 6),
+            ]
+            .into_iter()
+            .map(|(pos, number)| Action::RemovePencilmarks {
+                figure: vec![pos].into(),
+                pencilmarks: vec![number],
+            })
+            .collect(),
+        );
+
+        test_helpful_method(&grid, Aic::new().max_length(1), BTreeSet::new());
+    }
+
+    #[test]
+    fn placements() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .-------------.----------------.-----------------.
+            | 5   7   3   | 148  9     18  | 6      12   24  |
+            | 8   2   9   | 6    14    3   | 47     17   5   |
+            | 6   14  14  | 7    5     2   | 8      9    3   |
+            :-------------+----------------+-----------------:
+            | 1   6   2   | 48   48    9   | 5      3    7   |
+            | 4   8   5   | 3    17    17  | 29     26   269 |
+            | 3   9   7   | 2    6     5   | 1      4    8   |
+            :-------------+----------------+-----------------:
+            | 2   5   146 | 19   3     167 | 479    8    469 |
+            | 79  34  468 | 89   278   678 | 23479  5    1   |
+            | 79  13  168 | 5    1278  4   | 2379   267  269 |
+            '-------------'----------------'-----------------'
+            ",
+        )
+        .unwrap();
+
+        let mut actions: BTreeSet<Action> = [
+            (3, 8),
+            (5, 1),
+            (40, 1),
+            (41, 7),
+            (59, 1),
+            (62, 6),
+            (63, 9),
+            (67, 7),
+            (68, 8),
+            (69, 7),
+            (72, 7),
+            (74, 6),
+            (76, 7),
+            (78, 9),
+            (80, 9),
+        ]
+        .into_iter()
+        .map(|(pos, number)| Action::RemovePencilmarks {
+            figure: vec![pos].into(),
+            pencilmarks: vec![number],
+        })
+        .collect();
+
+        actions.insert(Action::PlaceNumber {
+            position: 40,
+            number: 7,
+        });
+        actions.insert(Action::PlaceNumber {
+            position: 41,
+            number: 1,
+        });
+
+        test_helpful_method(&grid, Aic::new(), actions);
+    }
+}
@@ -0,0 +1,407 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use itertools::Itertools;
+
+use crate::{
+    action::Action,
+    cell::Cell,
+    figure::Figure,
+    grid::{Als, Grid},
+    pencilmarks::Pencilmarks,
+};
+
+use super::Method;
+
+/// Pattern of Almost Locked Sets (see [`Grid::almost_locked_sets`]).
+///
+/// Number is restricted common between two disjoint sets, if every cell with it in one set
+/// sees every cell with it in the other set: it can be true in only one of the sets.
+/// If the set loses one of its pencilmarks, it becomes locked: every other pencilmark is true in it.
+#[derive(Clone, Copy, Debug)]
+pub enum AlsPattern {
+    /// Two sets with restricted common X. One of them loses X, so common Z is true in
+    /// one of the sets: it can be removed from cells, which see every Z of both sets.
+    /// If sets have two restricted commons (doubly linked), each set is locked without them,
+    /// and each restricted common is true in one of the sets.
+    XZ,
+    /// Sets A and B, linked to set C by restricted commons X and Y.
+    /// C can't lose both X and Y, so A or B is locked: common Z of A and B
+    /// can be removed from cells, which see every Z of both sets.
+    XYWing,
+    /// Stem cell and a set (petal) for every pencilmark of the stem, where every cell with
+    /// this pencilmark sees the stem. Stem is true, so one of the petals is locked:
+    /// Z, common to every petal, can be removed from cells, which see every Z of the petals.
+    DeathBlossom,
+}
+
+/// Methods on Almost Locked Sets, which use sets of up to `max_size` cells.
+#[derive(Clone, Debug)]
+pub struct AlmostLockedSets {
+    pattern: AlsPattern,
+    max_size: usize,
+}
+
+impl AlmostLockedSets {
+    /// Returns the method, which uses sets of up to 6 cells.
+    pub fn new(pattern: AlsPattern) -> Self {
+        Self {
+            pattern,
+            max_size: 6,
+        }
+    }
+
+    /// Maximum number of cells in the set.
+    /// Number of sets grows fast with it, and ALS-XY-Wing and Death Blossom
+    /// combine several sets, so the search time grows even faster.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+}
+
+/// Returns cells, which see every cell of the figure (excluding the figure itself).
+fn seen_by_all(figure: &Figure) -> Figure {
+    figure.iter().fold(Figure::all_cells(), |res, pos| {
+        res.intersection(Figure::neighbours(pos))
+    }) - figure.clone()
+}
+
+/// Returns `true` if every cell of `a` sees every cell of `b`.
+fn all_see(a: &Figure, b: &Figure) -> bool {
+    a.iter()
+        .all(|pos| !b.contains(pos) && b.difference(Figure::neighbours(pos)).is_empty())
+}
+
+fn restricted_commons(grid: &Grid, a: &Als, b: &Als) -> Vec<u8> {
+    a.pencilmarks
+        .intersection(b.pencilmarks)
+        .iter()
+        .filter(|&number| all_see(&a.cells_with(grid, number), &b.cells_with(grid, number)))
+        .collect()
+}
+
+fn is_disjoint(a: &Als, b: &Als) -> bool {
+    a.cells.intersection(b.cells.clone()).is_empty()
+}
+
+/// Removes number from cells, which see every cell of `cells` and are not in `excluded`.
+fn removal(grid: &Grid, cells: Figure, number: u8, excluded: Figure) -> Option<Action> {
+    let figure: Figure = (seen_by_all(&cells) - excluded)
+        .iter()
+        .filter(|&pos| grid[pos].pencilmarks_mask().contains(&number))
+        .collect();
+
+    (!figure.is_empty()).then_some(Action::RemovePencilmarks {
+        figure,
+        pencilmarks: vec![number],
+    })
+}
+
+impl AlsPattern {
+    fn xz(grid: &Grid, sets: &[Als]) -> Vec<Action> {
+        let mut res = vec![];
+
+        for (a, b) in sets.iter().tuple_combinations() {
+            if !is_disjoint(a, b) {
+                continue;
+            }
+
+            let rccs = restricted_commons(grid, a, b);
+
+            if rccs.is_empty() {
+                continue;
+            }
+
+            let both = a.cells.union(b.cells.clone());
+
+            for z in a.pencilmarks.intersection(b.pencilmarks) {
+                if rccs.len() == 1 && rccs.contains(&z) {
+                    continue;
+                }
+
+                let cells = a.cells_with(grid, z) + b.cells_with(grid, z);
+                res.extend(removal(grid, cells, z, both.clone()));
+            }
+
+            if rccs.len() < 2 {
+                continue;
+            }
+
+            // Doubly linked
+            for set in [a, b] {
+                for number in set.pencilmarks {
+                    if !rccs.contains(&number) {
+                        let cells = set.cells_with(grid, number);
+                        res.extend(removal(grid, cells, number, set.cells.clone()));
+                    }
+                }
+            }
+        }
+
+        res
+    }
+
+    fn xy_wing(grid: &Grid, sets: &[Als]) -> Vec<Action> {
+        let mut res = vec![];
+
+        for c in sets {
+            let linked: Vec<(&Als, Vec<u8>)> = sets
+                .iter()
+                .filter(|set| is_disjoint(set, c))
+                .map(|set| (set, restricted_commons(grid, set, c)))
+                .filter(|(_, rccs)| !rccs.is_empty())
+                .collect();
+
+            for ((a, a_rccs), (b, b_rccs)) in linked.iter().tuple_combinations() {
+                if !is_disjoint(a, b) {
+                    continue;
+                }
+
+                for (&x, &y) in a_rccs.iter().cartesian_product(b_rccs) {
+                    if x == y {
+                        continue;
+                    }
+
+                    for z in a.pencilmarks.intersection(b.pencilmarks) {
+                        if z == x || z == y {
+                            continue;
+                        }
+
+                        let cells = a.cells_with(grid, z) + b.cells_with(grid, z);
+                        let excluded = a.cells.union(b.cells.clone());
+                        res.extend(removal(grid, cells, z, excluded));
+                    }
+                }
+            }
+        }
+
+        res
+    }
+
+    fn death_blossom(grid: &Grid, sets: &[Als]) -> Vec<Action> {
+        let mut res = vec![];
+
+        for stem in Figure::all_cells() {
+            let Cell::Pencilmarks(pencilmarks) = grid[stem] else {
+                continue;
+            };
+
+            if pencilmarks.len() < 2 {
+                continue;
+            }
+
+            let petals: Vec<Vec<&Als>> = pencilmarks
+                .iter()
+                .map(|number| {
+                    sets.iter()
+                        .filter(|set| {
+                            set.pencilmarks.contains(&number)
+                                && all_see(&set.cells_with(grid, number), &vec![stem].into())
+                        })
+                        .collect()
+                })
+                .collect();
+
+            Self::choose_petals(
+                grid,
+                stem,
+                &petals,
+                &mut vec![],
+                Pencilmarks::all() - pencilmarks,
+                &mut res,
+            );
+        }
+
+        res
+    }
+
+    /// Recursively chooses disjoint petal for every pencilmark of the stem.
+    /// `common` is pencilmarks of every chosen petal, except pencilmarks of the stem.
+    fn choose_petals<'a>(
+        grid: &Grid,
+        stem: usize,
+        petals: &[Vec<&'a Als>],
+        chosen: &mut Vec<&'a Als>,
+        common: Pencilmarks,
+        res: &mut Vec<Action>,
+    ) {
+        if common.is_empty() {
+            return;
+        }
+
+        let Some((options, rest)) = petals.split_first() else {
+            let excluded = chosen.iter().fold(Figure::from(vec![stem]), |res, petal| {
+                res.union(petal.cells.clone())
+            });
+
+            for z in common {
+                let cells = chosen.iter().fold(Figure::default(), |res, petal| {
+                    res + petal.cells_with(grid, z)
+                });
+
+                res.extend(removal(grid, cells, z, excluded.clone()));
+            }
+
+            return;
+        };
+
+        for petal in options {
+            if chosen.iter().all(|other| is_disjoint(petal, other)) {
+                chosen.push(petal);
+                Self::choose_petals(
+                    grid,
+                    stem,
+                    rest,
+                    chosen,
+                    common.intersection(petal.pencilmarks),
+                    res,
+                );
+                chosen.pop();
+            }
+        }
+    }
+}
+
+impl Display for AlmostLockedSets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self.pattern {
+                AlsPattern::XZ => "ALS-XZ",
+                AlsPattern::XYWing => "ALS-XY-Wing",
+                AlsPattern::DeathBlossom => "Death Blossom",
+            }
+        )
+    }
+}
+
+impl Method for AlmostLockedSets {
+    fn get_all_applications(&self, grid: &Grid) -> BTreeSet<Action> {
+        let sets = grid.almost_locked_sets(self.max_size);
+
+        match self.pattern {
+            AlsPattern::XZ => AlsPattern::xz(grid, &sets),
+            AlsPattern::XYWing => AlsPattern::xy_wing(grid, &sets),
+            AlsPattern::DeathBlossom => AlsPattern::death_blossom(grid, &sets),
+        }
+        .into_iter()
+        .collect()
+    }
+
+    fn difficulty(&self) -> u32 {
+        match self.pattern {
+            AlsPattern::XZ => 410,
+            AlsPattern::XYWing => 420,
+            AlsPattern::DeathBlossom => 430,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::methods::test_helpful_method;
+
+    use super::*;
+
+    fn xz_grid() -> Grid {
+        Grid::from_candidate_grid(
+            "
+                .--------------------.----------------.------------------.
+                | 18      138    7   | 258  235   9   | 456   1456  46   |
+                | 189     6      389 | 58   4     35  | 7     15    2    |
+                | 5       4      2   | 7    6     1   | 3     8     9    |
+                :--------------------+----------------+------------------:
+                | 246789  5      49  | 1    279   48  | 246   2467  3    |
+                | 24679   27     1   | 69   2379  34  | 8     2467  5    |
+                | 3       278    48  | 256  257   458 | 1     9     467  |
+                :--------------------+----------------+------------------:
+                | 24      23     6   | 59   8     7   | 2459  2345  1    |
+                | 1478    9      458 | 3    15    2   | 456   4567  4678 |
+                | 1278    12378  358 | 4    159   6   | 259   2357  78   |
+                '--------------------'----------------'------------------'
+                ",
+        )
+        .unwrap()
+    }
+
+    fn xy_wing_grid() -> Grid {
+        Grid::from_candidate_grid(
+            "
+                .-----------------.----------------.--------------------.
+                | 567   4567  3   | 2    1    458  | 4578   458    9    |
+                | 8     4579  245 | 45   3    459  | 6      1245   1457 |
+                | 59    1     245 | 7    6    4589 | 23458  23458  345  |
+                :-----------------+----------------+--------------------:
+                | 2569  569   1   | 8    245  3    | 459    7      456  |
+                | 4     3     7   | 56   9    1    | 25     256    8    |
+                | 2569  8     56  | 456  245  7    | 1      349    346  |
+                :-----------------+----------------+--------------------:
+                | 1     2     456 | 9    8    45   | 3457   3456   3457 |
+                | 3     45    9   | 1    7    6    | 458    458    2    |
+                | 567   4567  8   | 3    45   2    | 459    14569  1456 |
+                '-----------------'----------------'--------------------'
+                ",
+        )
+        .unwrap()
+    }
+
+    fn death_blossom_grid() -> Grid {
+        Grid::from_candidate_grid(
+            "
+                .--------------.----------------.-------------------.
+                | 8    12  7   | 4    5     9   | 3      12    6    |
+                | 4    6   3   | 17   127   27  | 8      5     9    |
+                | 59   12  59  | 3    6     8   | 24     7     14   |
+                :--------------+----------------+-------------------:
+                | 1    5   8   | 6    2479  237 | 2479   249   347  |
+                | 679  3   2   | 8    1479  157 | 45679  469   457  |
+                | 679  47  49  | 579  279   235 | 1      2689  358  |
+                :--------------+----------------+-------------------:
+                | 2    9   145 | 57   3     6   | 457    48    1458 |
+                | 3    78  15  | 2    789   4   | 5679   169   157  |
+                | 57   48  6   | 159  18    157 | 459    3     2    |
+                '--------------'----------------'-------------------'
+                ",
+        )
+        .unwrap()
+    }
+
+    fn removal(pos: usize, number: u8) -> BTreeSet<Action> {
+        BTreeSet::from([Action::RemovePencilmarks {
+            figure: vec![pos].into(),
+            pencilmarks: vec![number],
+        }])
+    }
+
+    #[test]
+    fn xz() {
+        let method = AlmostLockedSets::new(AlsPattern::XZ);
+
+        test_helpful_method(&xz_grid(), method.clone(), removal(60, 4));
+        test_helpful_method(&xz_grid(), method.clone().max_size(3), BTreeSet::new());
+        test_helpful_method(&xy_wing_grid(), method, BTreeSet::new());
+    }
+
+    #[test]
+    fn xy_wing() {
+        let method = AlmostLockedSets::new(AlsPattern::XYWing);
+
+        test_helpful_method(&xy_wing_grid(), method.clone(), removal(26, 3));
+        test_helpful_method(&xy_wing_grid(), method.clone().max_size(5), BTreeSet::new());
+        test_helpful_method(&death_blossom_grid(), method, BTreeSet::new());
+    }
+
+    #[test]
+    fn death_blossom() {
+        let method = AlmostLockedSets::new(AlsPattern::DeathBlossom);
+
+        test_helpful_method(&death_blossom_grid(), method.clone(), removal(77, 5));
+        test_helpful_method(
+            &death_blossom_grid(),
+            method.clone().max_size(5),
+            BTreeSet::new(),
+        );
+        test_helpful_method(&xy_wing_grid(), method, BTreeSet::new());
+    }
+}
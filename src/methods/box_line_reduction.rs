@@ -13,12 +13,10 @@ pub enum BoxLineReduction {
 impl BoxLineReduction {
     fn find_in_figure(&self, grid: &Grid, figure: &Figure, dimension: usize) -> BTreeSet<Action> {
         let mut res = BTreeSet::new();
-        let pencilmarks_info = grid.pencilmarks_info(figure.clone());
-
-        for (pencilmark, positions) in pencilmarks_info {
-            if positions.len() == dimension {
-                let pencilmarks_figure: Figure = positions.into();
+        let pencilmarks_info = grid.pencilmarks_positions(figure.clone());
 
+        for (pencilmark, pencilmarks_figure) in pencilmarks_info {
+            if pencilmarks_figure.len() == dimension {
                 if let Some(sqr) = pencilmarks_figure.is_on_the_same_sqr() {
                     res.insert(Action::RemovePencilmarks {
                         figure: Figure::sqr(sqr) - pencilmarks_figure,
@@ -60,6 +58,13 @@ impl Method for BoxLineReduction {
 
         res
     }
+
+    fn difficulty(&self) -> u32 {
+        match self {
+            BoxLineReduction::Pair => 50,
+            BoxLineReduction::Triple => 60,
+        }
+    }
 }
 
 #[cfg(test)]
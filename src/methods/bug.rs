@@ -0,0 +1,183 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use crate::{action::Action, cell::Cell, figure::Figure, grid::Grid};
+
+use super::Method;
+
+/// Bivalue Universal Grave + 1.
+/// If every unsolved cell has two pencilmarks, except one cell with three,
+/// the puzzle would have multiple solutions without the extra pencilmark of that cell.
+/// The extra pencilmark is the one, which appears three times in the cell's row, col and sqr.
+/// Without it every pencilmark has to appear exactly twice in every unit (or not at all).
+#[derive(Clone, Debug)]
+pub struct BugPlusOne;
+
+impl BugPlusOne {
+    /// Returns `true` if grid is a BUG, when the number is removed from pencilmarks at the position.
+    fn is_bug_without(grid: &Grid, pos: usize, number: u8) -> bool {
+        Figure::all_figures().into_iter().all(|unit| {
+            grid.pencilmarks_positions(unit.clone())
+                .iter()
+                .all(|(pencilmark, positions)| {
+                    let mut count = positions.len();
+
+                    if pencilmark == number && unit.contains(pos) {
+                        count -= 1;
+                    }
+
+                    count == 0 || count == 2
+                })
+        })
+    }
+}
+
+impl Display for BugPlusOne {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BUG+1")
+    }
+}
+
+impl Method for BugPlusOne {
+    fn get_all_applications(&self, grid: &Grid) -> BTreeSet<Action> {
+        let mut res = BTreeSet::new();
+        let mut trivalue = None;
+
+        for pos in Figure::all_cells() {
+            match grid[pos] {
+                Cell::Number(_) => (),
+                Cell::Pencilmarks(p) if p.len() == 2 => (),
+                Cell::Pencilmarks(p) if p.len() == 3 && trivalue.is_none() => {
+                    trivalue = Some((pos, p));
+                }
+                Cell::Pencilmarks(_) => return res,
+            }
+        }
+
+        let Some((pos, pencilmarks)) = trivalue else {
+            return res;
+        };
+
+        let units = [
+            Figure::row(Figure::row_of(pos)),
+            Figure::col(Figure::col_of(pos)),
+            Figure::sqr(Figure::sqr_of(pos)),
+        ];
+
+        for number in pencilmarks {
+            let appears_three_times = units.iter().all(|unit| {
+                grid.pencilmarks_positions(unit.clone())
+                    .get(number)
+                    .is_some_and(|positions| positions.len() == 3)
+            });
+
+            if appears_three_times && Self::is_bug_without(grid, pos, number) {
+                res.insert(Action::PlaceNumber {
+                    position: pos,
+                    number,
+                });
+            }
+        }
+
+        res
+    }
+
+    fn difficulty(&self) -> u32 {
+        270
+    }
+
+    fn is_uniqueness_based(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{methods::test_helpful_method, solver::Solver};
+
+    use super::*;
+
+    #[test]
+    fn bug_plus_one() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .-------------.----------.------------.
+            | 5    7   3  | 4   2  8 | 69  1   69 |
+            | 1    8   2  | 7   9  6 | 3   5   4  |
+            | 46   69  49 | 1   3  5 | 2   8   7  |
+            :-------------+----------+------------:
+            | 34   5   49 | 2   6  7 | 8   39  1  |
+            | 67   2   8  | 9   1  3 | 4   67  5  |
+            | 367  69  1  | 8   5  4 | 67  2   39 |
+            :-------------+----------+------------:
+            | 8    3   6  | 5   4  1 | 79  79  2  |
+            | 9    1   7  | 36  8  2 | 5   4   36 |
+            | 2    4   5  | 36  7  9 | 1   36  8  |
+            '-------------'----------'------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            BugPlusOne,
+            BTreeSet::from([Action::PlaceNumber {
+                position: 45,
+                number: 6,
+            }]),
+        );
+
+        let grid = Grid::from_candidate_grid(
+            "
+            .-----------.-------------.-----------.
+            | 6   9  1  | 8    2   3  | 4   5   7 |
+            | 45  8  2  | 59   7   49 | 3   6   1 |
+            | 45  3  7  | 156  15  46 | 8   2   9 |
+            :-----------+-------------+-----------:
+            | 2   4  8  | 7    15  69 | 56  19  3 |
+            | 3   7  69 | 15   4   2  | 56  19  8 |
+            | 1   5  69 | 69   3   8  | 2   7   4 |
+            :-----------+-------------+-----------:
+            | 9   2  4  | 3    6   1  | 7   8   5 |
+            | 8   6  5  | 4    9   7  | 1   3   2 |
+            | 7   1  3  | 2    8   5  | 9   4   6 |
+            '-----------'-------------'-----------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            BugPlusOne,
+            BTreeSet::from([Action::PlaceNumber {
+                position: 21,
+                number: 5,
+            }]),
+        );
+
+        let mut solver = Solver::all_methods();
+        solver.set_uniqueness_methods(false);
+
+        assert!(solver
+            .first_applicable(&grid)
+            .is_none_or(|(method, _)| method.to_string() != "BUG+1"));
+    }
+
+    #[test]
+    fn not_a_bug() {
+        let mut grid = Grid::from_str(&"0".repeat(81)).unwrap();
+
+        // 1 appears three times in every unit of the trivalue cell,
+        // but 5 and 6 appear more than twice in most units
+        for pos in Figure::all_cells() {
+            grid.set_pencilmarks(pos, vec![5, 6]);
+        }
+        grid.set_pencilmarks(0, vec![1, 2, 3]);
+        for pos in [3, 6, 10, 20, 27, 54] {
+            grid.set_pencilmarks(pos, vec![1, 4]);
+        }
+
+        test_helpful_method(&grid, BugPlusOne, BTreeSet::new());
+    }
+}
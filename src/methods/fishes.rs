@@ -53,24 +53,56 @@ impl Candidate {
     }
 }
 
+/// Finned fishes have extra candidates (fins) in the base lines, all in one sqr.
+/// Either the fish is true, or one of the fins is: so the number can be removed only
+/// from the cells of the cover lines, which see every fin (lay in the fin's sqr).
+///
+/// Sashimi fishes are finned fishes, which would not be a fish without the fins.
 #[derive(Clone, Debug)]
 pub enum Fishes {
     XWing,
     Swordfish,
     Jellyfish,
+    FinnedXWing,
+    FinnedSwordfish,
+    FinnedJellyfish,
+    SashimiXWing,
+    SashimiSwordfish,
+    SashimiJellyfish,
+}
+
+#[derive(PartialEq)]
+enum Fins {
+    None,
+    Finned,
+    Sashimi,
 }
 
 impl Fishes {
+    fn dimensions(&self) -> usize {
+        match self {
+            Fishes::XWing | Fishes::FinnedXWing | Fishes::SashimiXWing => 2,
+            Fishes::Swordfish | Fishes::FinnedSwordfish | Fishes::SashimiSwordfish => 3,
+            Fishes::Jellyfish | Fishes::FinnedJellyfish | Fishes::SashimiJellyfish => 4,
+        }
+    }
+
+    fn fins(&self) -> Fins {
+        match self {
+            Fishes::XWing | Fishes::Swordfish | Fishes::Jellyfish => Fins::None,
+            Fishes::FinnedXWing | Fishes::FinnedSwordfish | Fishes::FinnedJellyfish => Fins::Finned,
+            Fishes::SashimiXWing | Fishes::SashimiSwordfish | Fishes::SashimiJellyfish => {
+                Fins::Sashimi
+            }
+        }
+    }
+
     /// If not rotated, search in columns.
     /// If rotated, change every row to column, and vise versa. Logic does not changes.
     fn get_all_in_row_or_col(&self, grid: &Grid, rotated: bool) -> BTreeSet<Action> {
         let mut res = BTreeSet::new();
 
-        let dimensions = match self {
-            Fishes::XWing => 2,
-            Fishes::Swordfish => 3,
-            Fishes::Jellyfish => 4,
-        };
+        let dimensions = self.dimensions();
 
         let mut numbers_to_candidates: HashMap<u8, Vec<Candidate>> = HashMap::new();
 
@@ -81,14 +113,11 @@ impl Fishes {
                 Figure::col(i)
             };
 
-            let info = grid.pencilmarks_info(column);
+            let info = grid.pencilmarks_positions(column);
 
             let figure_candidates = info.iter().filter_map(|(pencilmark, positions)| {
                 if (2..=dimensions).contains(&positions.len()) {
-                    Some((
-                        *pencilmark,
-                        Candidate::new(positions.clone().into(), rotated),
-                    ))
+                    Some((pencilmark, Candidate::new(positions.clone(), rotated)))
                 } else {
                     None
                 }
@@ -135,6 +164,108 @@ impl Fishes {
 
         res
     }
+
+    /// Same as [`Self::get_all_in_row_or_col`], but for finned and sashimi fishes.
+    /// Base lines are columns (rows if rotated), cover lines are rows (columns if rotated).
+    fn get_all_finned_in_row_or_col(&self, grid: &Grid, rotated: bool) -> BTreeSet<Action> {
+        let mut res = BTreeSet::new();
+
+        let dimensions = self.dimensions();
+
+        let base_line = |i: u8| {
+            if rotated {
+                Figure::row(i)
+            } else {
+                Figure::col(i)
+            }
+        };
+        let cover_line = |i: u8| {
+            if rotated {
+                Figure::col(i)
+            } else {
+                Figure::row(i)
+            }
+        };
+        let cover_line_of = |pos: usize| {
+            if rotated {
+                Figure::col_of(pos)
+            } else {
+                Figure::row_of(pos)
+            }
+        };
+
+        // Number to (base line, positions of the number in it)
+        let mut numbers_to_lines: HashMap<u8, Vec<(Figure, Figure)>> = HashMap::new();
+
+        for i in 0..9 {
+            for (number, positions) in grid.pencilmarks_positions(base_line(i)) {
+                numbers_to_lines
+                    .entry(number)
+                    .or_default()
+                    .push((base_line(i), positions));
+            }
+        }
+
+        for (number, lines) in numbers_to_lines {
+            for base in lines.iter().combinations(dimensions) {
+                let base_lines = base
+                    .iter()
+                    .fold(Figure::default(), |union, (line, _)| union + line.clone());
+                let positions = base
+                    .iter()
+                    .fold(Figure::default(), |union, (_, p)| union + p.clone());
+
+                let cover_line_numbers = positions.iter().map(cover_line_of).unique();
+
+                for cover in cover_line_numbers.combinations(dimensions) {
+                    let cover_lines = cover
+                        .into_iter()
+                        .fold(Figure::default(), |union, i| union + cover_line(i));
+
+                    let fins = positions.difference(cover_lines.clone());
+
+                    let Some(sqr) = fins.is_on_the_same_sqr() else {
+                        continue;
+                    };
+
+                    // Positions of the base lines without fins
+                    let bodies: Vec<Candidate> = base
+                        .iter()
+                        .map(|(_, p)| Candidate::new(p.intersection(cover_lines.clone()), rotated))
+                        .collect();
+
+                    if bodies.iter().any(|body| body.figure.is_empty()) {
+                        continue;
+                    }
+
+                    let is_fish_without_fins =
+                        Candidate::do_candidates_match(bodies.iter().collect(), dimensions)
+                            .is_some();
+
+                    let fins_kind = if is_fish_without_fins {
+                        Fins::Finned
+                    } else {
+                        Fins::Sashimi
+                    };
+
+                    if fins_kind != self.fins() {
+                        continue;
+                    }
+
+                    let figure = cover_lines.intersection(Figure::sqr(sqr)) - base_lines.clone();
+
+                    if !figure.is_empty() {
+                        res.insert(Action::RemovePencilmarks {
+                            figure,
+                            pencilmarks: vec![number],
+                        });
+                    }
+                }
+            }
+        }
+
+        res
+    }
 }
 
 impl Display for Fishes {
@@ -143,17 +274,45 @@ impl Display for Fishes {
             Fishes::XWing => write!(f, "X-Wing"),
             Fishes::Swordfish => write!(f, "Swordfish"),
             Fishes::Jellyfish => write!(f, "Jellyfish"),
+            Fishes::FinnedXWing => write!(f, "Finned X-Wing"),
+            Fishes::FinnedSwordfish => write!(f, "Finned Swordfish"),
+            Fishes::FinnedJellyfish => write!(f, "Finned Jellyfish"),
+            Fishes::SashimiXWing => write!(f, "Sashimi X-Wing"),
+            Fishes::SashimiSwordfish => write!(f, "Sashimi Swordfish"),
+            Fishes::SashimiJellyfish => write!(f, "Sashimi Jellyfish"),
         }
     }
 }
 
 impl Method for Fishes {
     fn get_all_applications(&self, grid: &Grid) -> BTreeSet<Action> {
-        let mut res = self.get_all_in_row_or_col(grid, false);
+        if self.fins() == Fins::None {
+            let mut res = self.get_all_in_row_or_col(grid, false);
 
-        res.append(&mut self.get_all_in_row_or_col(grid, true));
+            res.append(&mut self.get_all_in_row_or_col(grid, true));
 
-        res
+            res
+        } else {
+            let mut res = self.get_all_finned_in_row_or_col(grid, false);
+
+            res.append(&mut self.get_all_finned_in_row_or_col(grid, true));
+
+            res
+        }
+    }
+
+    fn difficulty(&self) -> u32 {
+        match self {
+            Fishes::XWing => 200,
+            Fishes::Swordfish => 250,
+            Fishes::Jellyfish => 300,
+            Fishes::FinnedXWing => 340,
+            Fishes::SashimiXWing => 350,
+            Fishes::FinnedSwordfish => 360,
+            Fishes::SashimiSwordfish => 370,
+            Fishes::FinnedJellyfish => 380,
+            Fishes::SashimiJellyfish => 390,
+        }
     }
 }
 
@@ -163,7 +322,7 @@ mod tests {
 
     use crate::grid::Grid;
     use crate::methods::fishes::Fishes::{Jellyfish, Swordfish, XWing};
-    use crate::methods::test_method;
+    use crate::methods::{test_helpful_method, test_method};
 
     use super::*;
 
@@ -354,4 +513,178 @@ mod tests {
 
         assert_eq!(actions, assertion);
     }
+
+    #[test]
+    fn finned_fishes() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .-----------------.-----------.------------------.
+            | 2     14   45   | 15  8  9  | 36    7    36    |
+            | 3     15   7    | 2   6  4  | 8     9    15    |
+            | 6     9    8    | 15  3  7  | 14    45   2     |
+            :-----------------+-----------+------------------:
+            | 5     6    2    | 7   9  1  | 34    8    34    |
+            | 4789  478  49   | 6   2  3  | 59    1    579   |
+            | 79    3    1    | 8   4  5  | 2     6    79    |
+            :-----------------+-----------+------------------:
+            | 1     2    469  | 39  5  68 | 7     34   4689  |
+            | 489   48   4569 | 39  7  2  | 1469  345  14689 |
+            | 789   578  3    | 4   1  68 | 569   2    5689  |
+            '-----------------'-----------'------------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            Fishes::FinnedXWing,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![62, 71].into(),
+                pencilmarks: vec![9],
+            }]),
+        );
+
+        let grid = Grid::from_candidate_grid(
+            "
+            .-----------------.----------------.---------------------.
+            | 567   4567  3   | 2    1    458  | 4578   458    9     |
+            | 8     4579  245 | 45   3    459  | 6      1245   1457  |
+            | 259   1     245 | 7    6    4589 | 23458  23458  345   |
+            :-----------------+----------------+---------------------:
+            | 2569  569   1   | 8    245  3    | 459    7      456   |
+            | 4     3     7   | 56   9    1    | 25     256    8     |
+            | 2569  8     256 | 456  245  7    | 1      34569  3456  |
+            :-----------------+----------------+---------------------:
+            | 1     2     456 | 9    8    45   | 3457   3456   34567 |
+            | 35    45    9   | 13   7    6    | 458    1458   2     |
+            | 3567  4567  8   | 13   45   2    | 459    14569  1456  |
+            '-----------------'----------------'---------------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            Fishes::FinnedSwordfish,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![62].into(),
+                pencilmarks: vec![6],
+            }]),
+        );
+
+        let grid = Grid::from_candidate_grid(
+            "
+            .----------------.--------------.------------------.
+            | 4    235   23  | 1   7     8  | 6     25    9    |
+            | 9    6     17  | 5   3     2  | 14    178   1478 |
+            | 8    125   127 | 9   4     6  | 125   1257  3    |
+            :----------------+--------------+------------------:
+            | 3    4     128 | 7   1258  15 | 9     6     18   |
+            | 6    178   5   | 4   18    9  | 3     178   2    |
+            | 12   1278  9   | 6   128   3  | 15    4     1578 |
+            :----------------+--------------+------------------:
+            | 15   9     4   | 2   6     7  | 8     3     15   |
+            | 7    38    6   | 38  9     15 | 1245  125   45   |
+            | 125  12    38  | 38  15    4  | 7     9     6    |
+            '----------------'--------------'------------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            Fishes::FinnedJellyfish,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![35].into(),
+                pencilmarks: vec![1],
+            }]),
+        );
+    }
+
+    #[test]
+    fn sashimi_fishes() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .--------------.----------------.-------------------.
+            | 8   7    14  | 123  1259  349 | 6     1459  13459 |
+            | 6   2    9   | 137  15    347 | 13    145   8     |
+            | 14  5    3   | 8    6     49  | 2     7     149   |
+            :--------------+----------------+-------------------:
+            | 5   39   8   | 137  4     379 | 139   6     2     |
+            | 7   1    246 | 23   29    68  | 389   4589  3459  |
+            | 49  369  246 | 5    129   68  | 1378  1489  13479 |
+            :--------------+----------------+-------------------:
+            | 3   89   5   | 6    7     2   | 4     189   19    |
+            | 19  689  16  | 4    3     5   | 789   2     79    |
+            | 2   4    7   | 9    8     1   | 5     3     6     |
+            '--------------'----------------'-------------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            Fishes::SashimiXWing,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![52, 53].into(),
+                pencilmarks: vec![9],
+            }]),
+        );
+
+        let grid = Grid::from_candidate_grid(
+            "
+            .------------.------------------.-----------------.
+            | 3    5  17 | 168   1267  6789 | 289   4    128  |
+            | 127  8  9  | 3     4     57   | 6     25   125  |
+            | 12   4  6  | 158   125   589  | 2589  3    7    |
+            :------------+------------------+-----------------:
+            | 15   2  14 | 7     9     368  | 358   568  4568 |
+            | 57   6  47 | 1458  15    358  | 2358  9    2458 |
+            | 9    3  8  | 456   56    2    | 1     7    456  |
+            :------------+------------------+-----------------:
+            | 6    9  5  | 2     8     4    | 7     1    3    |
+            | 4    1  3  | 56    567   567  | 28    28   9    |
+            | 8    7  2  | 9     3     1    | 4     56   56   |
+            '------------'------------------'-----------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            Fishes::SashimiSwordfish,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![41].into(),
+                pencilmarks: vec![5],
+            }]),
+        );
+
+        let grid = Grid::from_candidate_grid(
+            "
+            .------------.------------------.-----------------.
+            | 2   1   36 | 3689  3578  4689 | 34568  3467  37 |
+            | 45  8   9  | 36    357   1    | 3456   3467  2  |
+            | 45  7   36 | 2     358   3468 | 34568  1     9  |
+            :------------+------------------+-----------------:
+            | 8   9   7  | 5     1     2    | 34     34    6  |
+            | 6   5   4  | 38    9     38   | 7      2     1  |
+            | 3   2   1  | 4     6     7    | 9      8     5  |
+            :------------+------------------+-----------------:
+            | 7   36  8  | 1     2     5    | 36     9     4  |
+            | 9   36  2  | 7     4     36   | 1      5     8  |
+            | 1   4   5  | 3689  38    3689 | 2      367   37 |
+            '------------'------------------'-----------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            Fishes::SashimiJellyfish,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![23].into(),
+                pencilmarks: vec![3],
+            }]),
+        );
+    }
 }
@@ -20,13 +20,13 @@ impl Hidden {
         let mut res = BTreeSet::new();
 
         for f in Figure::all_figures() {
-            grid.pencilmarks_info(f)
+            grid.pencilmarks_positions(f)
                 .iter()
                 .filter_map(|(pencilmark, positions)| {
                     if positions.len() == 1 {
                         Some(Action::PlaceNumber {
-                            position: positions[0],
-                            number: *pencilmark,
+                            position: positions.first().unwrap(),
+                            number: pencilmark,
                         })
                     } else {
                         None
@@ -47,7 +47,7 @@ impl Hidden {
             let mut candidates = vec![];
             let mut lead_positions = HashSet::new();
 
-            for (pencilmark, positions) in grid.pencilmarks_info(f.clone()) {
+            for (pencilmark, positions) in grid.pencilmarks_positions(f.clone()) {
                 if (2..=dimension).contains(&positions.len()) {
                     candidates.push((pencilmark, positions.clone()));
                 }
@@ -60,7 +60,7 @@ impl Hidden {
                 let mut pencilmarks = vec![];
 
                 for candidate in candidates.clone() {
-                    if candidate.1.iter().all(|&x| lead_position.contains(&x)) {
+                    if candidate.1.iter().all(|x| lead_position.contains(x)) {
                         pencilmarks.push(candidate.0);
                     }
                 }
@@ -69,7 +69,7 @@ impl Hidden {
 
                 if pencilmarks.len() == dimension {
                     res.insert(Action::PreservePencilmarks {
-                        figure: lead_position.to_vec().into(),
+                        figure: lead_position.clone(),
                         pencilmarks,
                     });
                 }
@@ -110,6 +110,15 @@ impl Method for Hidden {
             self.multiple_applications(grid, dimension)
         }
     }
+
+    fn difficulty(&self) -> u32 {
+        match self {
+            Hidden::Single => 15,
+            Hidden::Pair => 70,
+            Hidden::Triple => 100,
+            Hidden::Quad => 150,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,337 @@
+use std::{
+    collections::{hash_map::Entry, BTreeSet, HashMap, HashSet},
+    fmt::Display,
+};
+
+use crate::{action::Action, cell::Cell, figure::Figure, grid::Grid};
+
+use super::Method;
+
+/// Candidate: number in the cell.
+type Node = (usize, u8);
+
+/// Same idea as chain link in [`super::simple_coloring`], but links can also go
+/// to the other number of the bivalue cell.
+#[derive(Clone, Copy, Debug)]
+struct MedusaLink {
+    pos: usize,
+    number: u8,
+    is_colored: bool,
+}
+
+impl MedusaLink {
+    fn get_next(&self, grid: &Grid) -> Vec<Self> {
+        let mut res = vec![];
+
+        let figures = [
+            Figure::row(Figure::row_of(self.pos)),
+            Figure::col(Figure::col_of(self.pos)),
+            Figure::sqr(Figure::sqr_of(self.pos)),
+        ];
+
+        for figure in figures {
+            if let Some(positions) = grid.pencilmarks_positions(figure).get(self.number) {
+                if positions.len() == 2 {
+                    let pos = positions.iter().find(|&pos| pos != self.pos).unwrap();
+
+                    res.push(MedusaLink {
+                        pos,
+                        number: self.number,
+                        is_colored: !self.is_colored,
+                    });
+                }
+            }
+        }
+
+        if let Cell::Pencilmarks(pencilmarks) = grid[self.pos] {
+            if pencilmarks.len() == 2 {
+                let number = pencilmarks.iter().find(|&n| n != self.number).unwrap();
+
+                res.push(MedusaLink {
+                    pos: self.pos,
+                    number,
+                    is_colored: !self.is_colored,
+                });
+            }
+        }
+
+        res
+    }
+
+    /// Returns every node, connected to this one, with its color.
+    fn get_cluster(&self, grid: &Grid) -> HashMap<Node, bool> {
+        let mut res = HashMap::from([((self.pos, self.number), self.is_colored)]);
+        let mut stack = vec![*self];
+
+        while let Some(link) = stack.pop() {
+            for next in link.get_next(grid) {
+                if let Entry::Vacant(entry) = res.entry((next.pos, next.number)) {
+                    entry.insert(next.is_colored);
+                    stack.push(next);
+                }
+            }
+        }
+
+        res
+    }
+}
+
+/// Coloring of candidates (not only cells), linked either by strong link in unit,
+/// or by bivalue cell. One of two colors is true.
+///
+/// Color is false (and the other color is placed), if:
+/// - Rule 1: it appears twice in one cell.
+/// - Rule 2: it appears twice with the same number in one unit.
+/// - Rule 6: every candidate of some uncolored cell sees the same number in this color.
+///
+/// Uncolored candidate can be removed, if:
+/// - Rule 3: its cell has both colors.
+/// - Rule 4: it sees the same number in both colors.
+/// - Rule 5: it sees the same number in one color, and its cell has the other color.
+#[derive(Clone, Debug)]
+pub struct Medusa;
+
+/// Returns `true` if `a` and `b` are different cells in the same unit.
+fn sees(a: usize, b: usize) -> bool {
+    a != b && Figure::neighbours(a).contains(b)
+}
+
+impl Medusa {
+    fn is_false(grid: &Grid, cluster: &HashMap<Node, bool>, color: bool) -> bool {
+        let nodes: Vec<Node> = cluster
+            .iter()
+            .filter(|(_, &c)| c == color)
+            .map(|(&node, _)| node)
+            .collect();
+
+        let sees_color =
+            |pos: usize, number: u8| nodes.iter().any(|&(p, n)| n == number && sees(pos, p));
+
+        // Rule 1 and Rule 2
+        let twice = nodes.iter().enumerate().any(|(i, &(pos1, number1))| {
+            nodes[i + 1..]
+                .iter()
+                .any(|&(pos2, number2)| pos1 == pos2 || (number1 == number2 && sees(pos1, pos2)))
+        });
+
+        // Rule 6
+        let empties_cell = Figure::all_cells().into_iter().any(|pos| match grid[pos] {
+            Cell::Pencilmarks(pencilmarks) => pencilmarks
+                .iter()
+                .all(|number| !cluster.contains_key(&(pos, number)) && sees_color(pos, number)),
+            Cell::Number(_) => false,
+        });
+
+        twice || empties_cell
+    }
+
+    fn removals(grid: &Grid, cluster: &HashMap<Node, bool>) -> Vec<Action> {
+        let mut res = vec![];
+
+        let color_in_cell =
+            |pos: usize, color: bool| cluster.iter().any(|(&(p, _), &c)| p == pos && c == color);
+        let sees_color = |pos: usize, number: u8, color: bool| {
+            cluster
+                .iter()
+                .any(|(&(p, n), &c)| n == number && c == color && sees(pos, p))
+        };
+
+        for pos in Figure::all_cells() {
+            let Cell::Pencilmarks(pencilmarks) = grid[pos] else {
+                continue;
+            };
+
+            let both_colors_in_cell = color_in_cell(pos, false) && color_in_cell(pos, true);
+
+            let removed: Vec<u8> = pencilmarks
+                .iter()
+                .filter(|&number| !cluster.contains_key(&(pos, number)))
+                .filter(|&number| {
+                    // Rule 3
+                    both_colors_in_cell
+                        // Rule 4
+                        || (sees_color(pos, number, false) && sees_color(pos, number, true))
+                        // Rule 5
+                        || [false, true].into_iter().any(|color| {
+                            sees_color(pos, number, color) && color_in_cell(pos, !color)
+                        })
+                })
+                .collect();
+
+            if !removed.is_empty() {
+                res.push(Action::RemovePencilmarks {
+                    figure: vec![pos].into(),
+                    pencilmarks: removed,
+                });
+            }
+        }
+
+        res
+    }
+}
+
+impl Display for Medusa {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "3D Medusa")
+    }
+}
+
+impl Method for Medusa {
+    fn get_all_applications(&self, grid: &Grid) -> BTreeSet<Action> {
+        let mut res = BTreeSet::new();
+        let mut colored: HashSet<Node> = HashSet::new();
+
+        for pos in Figure::all_cells() {
+            let Cell::Pencilmarks(pencilmarks) = grid[pos] else {
+                continue;
+            };
+
+            for number in pencilmarks {
+                if colored.contains(&(pos, number)) {
+                    continue;
+                }
+
+                let cluster = MedusaLink {
+                    pos,
+                    number,
+                    is_colored: false,
+                }
+                .get_cluster(grid);
+
+                colored.extend(cluster.keys());
+
+                if cluster.len() < 2 {
+                    continue;
+                }
+
+                let false_color = [false, true]
+                    .into_iter()
+                    .find(|&color| Self::is_false(grid, &cluster, color));
+
+                match false_color {
+                    Some(color) => {
+                        res.extend(cluster.iter().filter(|(_, &c)| c != color).map(
+                            |(&(position, number), _)| Action::PlaceNumber { position, number },
+                        ))
+                    }
+                    None => res.extend(Self::removals(grid, &cluster)),
+                }
+            }
+        }
+
+        res
+    }
+
+    fn difficulty(&self) -> u32 {
+        315
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::methods::test_helpful_method;
+
+    use super::*;
+
+    #[test]
+    fn removals() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .--------------.---------------.-----------------.
+            | 36   149  8  | 2   7    134  | 5    146    69  |
+            | 236  149  19 | 68  5    1348 | 148  7      269 |
+            | 26   7    5  | 9   46   148  | 148  12468  3   |
+            :--------------+---------------+-----------------:
+            | 4    8    3  | 7   9    6    | 2    5      1   |
+            | 5    16   16 | 3   8    2    | 7    9      4   |
+            | 7    29   29 | 4   1    5    | 6    3      8   |
+            :--------------+---------------+-----------------:
+            | 8    5    4  | 1   26   9    | 3    26     7   |
+            | 1    3    26 | 68  246  7    | 9    48     5   |
+            | 9    26   7  | 5   3    48   | 148  148    26  |
+            '--------------'---------------'-----------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            Medusa,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![22].into(),
+                pencilmarks: vec![6],
+            }]),
+        );
+
+        let grid = Grid::from_candidate_grid(
+            "
+            .------------------.--------------------.----------------.
+            | 37  56     1     | 267    8     256   | 35    9    4   |
+            | 9   568    4     | 16     3     56    | 7     18   2   |
+            | 37  2      58    | 179    1579  4     | 1358  6    135 |
+            :------------------+--------------------+----------------:
+            | 4   378    3789  | 5      679   1     | 2     78   36  |
+            | 2   137    378   | 3678   4     368   | 1368  5    9   |
+            | 6   13578  35789 | 23789  279   2389  | 4     178  13  |
+            :------------------+--------------------+----------------:
+            | 5   9      26    | 4      126   7     | 16    3    8   |
+            | 8   37     2367  | 1236   1256  236   | 9     4    156 |
+            | 1   4      36    | 3689   569   35689 | 56    2    7   |
+            '------------------'--------------------'----------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            Medusa,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![46].into(),
+                pencilmarks: vec![1],
+            }]),
+        );
+    }
+
+    #[test]
+    fn placements() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .---------------.---------------.---------.
+            | 3    7    2   | 1     9    5  | 8  4  6 |
+            | 8    9    4   | 3     6    7  | 5  2  1 |
+            | 1    5    6   | 28    4    28 | 7  9  3 |
+            :---------------+---------------+---------:
+            | 5    6    17  | 47    3    14 | 2  8  9 |
+            | 279  23   379 | 5     278  28 | 1  6  4 |
+            | 24   248  18  | 6     12   9  | 3  7  5 |
+            :---------------+---------------+---------:
+            | 6    1    89  | 789   78   3  | 4  5  2 |
+            | 249  248  5   | 2489  128  14 | 6  3  7 |
+            | 247  234  37  | 24    5    6  | 9  1  8 |
+            '---------------'---------------'---------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            Medusa,
+            [
+                (29, 1),
+                (30, 7),
+                (32, 4),
+                (36, 9),
+                (47, 8),
+                (49, 1),
+                (56, 9),
+                (58, 7),
+                (64, 8),
+                (66, 9),
+                (68, 1),
+            ]
+            .into_iter()
+            .map(|(position, number)| Action::PlaceNumber { position, number })
+            .collect(),
+        );
+    }
+}
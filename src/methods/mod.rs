@@ -1,12 +1,23 @@
+pub mod aic;
+pub mod als;
 pub mod box_line_reduction;
+pub mod bug;
 pub mod fishes;
 pub mod hidden_n;
+pub mod medusa;
 pub mod naked_n;
 pub mod pointing_ns;
+pub mod registry;
 pub mod simple_coloring;
+pub mod single_digit_patterns;
+pub mod unique_rectangles;
+pub mod wings;
+pub mod x_cycles;
+pub mod xy_chain;
 
 use dyn_clone::DynClone;
 use std::{
+    any::Any,
     collections::BTreeSet,
     fmt::{Debug, Display},
     str::FromStr,
@@ -16,8 +27,16 @@ use crate::{action::Action, grid::Grid};
 
 /// Methods follow the definition from https://sudokuwiki.org, if it is avalible
 /// for the given method.
-pub trait Method: Display + DynClone + Debug {
+pub trait Method: Display + DynClone + Debug + AsAny {
     fn get_all_applications(&self, grid: &Grid) -> BTreeSet<Action>;
+    /// Used for grading: the harder method is for a human, the bigger the difficulty.
+    /// See [`crate::grading::Tier`] for the ranges of difficulties.
+    fn difficulty(&self) -> u32;
+    /// Returns `true` if method assumes, that the puzzle has unique solution.
+    /// Such methods can give wrong results for puzzles with multiple solutions.
+    fn is_uniqueness_based(&self) -> bool {
+        false
+    }
     fn get_all_helpful_applications(&self, grid: &Grid, simplify: bool) -> BTreeSet<Action> {
         let mut applications = self.get_all_applications(grid);
 
@@ -42,6 +61,17 @@ pub trait Method: Display + DynClone + Debug {
 // Derive Clone for Box<dyn Method>
 dyn_clone::clone_trait_object!(Method);
 
+/// Lets `dyn Method` be downcast to the concrete method (see [`Any::downcast_ref`]).
+pub trait AsAny {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 // This is used for internal testing of the methods.
 // Rust does not mark functions used in tests as used,
 // so disable the warning
@@ -53,3 +83,11 @@ fn test_method<T: Method>(grid: &str, creator: T, valid_candidates: BTreeSet<Act
 
     assert_eq!(candidates, valid_candidates);
 }
+
+// Same as `test_method`, but checks only helpful and simplified applications.
+#[allow(dead_code)]
+fn test_helpful_method<T: Method>(grid: &Grid, creator: T, valid_candidates: BTreeSet<Action>) {
+    let candidates = creator.get_all_helpful_applications(grid, true);
+
+    assert_eq!(candidates, valid_candidates);
+}
@@ -24,7 +24,7 @@ impl Naked {
                 if pencilmarks.len() == 1 {
                     res.insert(Action::PlaceNumber {
                         position: i,
-                        number: pencilmarks[0],
+                        number: pencilmarks.iter().next().unwrap(),
                     });
                 }
             }
@@ -56,9 +56,9 @@ impl Naked {
                 let mut possible_positions = vec![];
 
                 for candidate in candidates.clone() {
-                    let pencilmarks = &grid[candidate].pencilmarks();
+                    let pencilmarks = grid[candidate].pencilmarks_mask();
 
-                    if pencilmarks.iter().all(|&x| lead_set.contains(&x)) {
+                    if (pencilmarks - *lead_set).is_empty() {
                         possible_positions.push(candidate);
                     }
                 }
@@ -105,6 +105,15 @@ impl Method for Naked {
             self.multiple_applications(grid, dimension)
         }
     }
+
+    fn difficulty(&self) -> u32 {
+        match self {
+            Naked::Single => 10,
+            Naked::Pair => 60,
+            Naked::Triple => 80,
+            Naked::Quad => 120,
+        }
+    }
 }
 
 #[cfg(test)]
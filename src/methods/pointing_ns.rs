@@ -33,12 +33,10 @@ impl Method for Pointing {
 
         for sqr_number in 0..9 {
             let sqr = Figure::sqr(sqr_number);
-            let pencilmarks_info = grid.pencilmarks_info(sqr);
-
-            for (pencilmark, positions) in pencilmarks_info {
-                if positions.len() == dimension {
-                    let pencilmarks_figure: Figure = positions.into();
+            let pencilmarks_info = grid.pencilmarks_positions(sqr);
 
+            for (pencilmark, pencilmarks_figure) in pencilmarks_info {
+                if pencilmarks_figure.len() == dimension {
                     let on_same_row = pencilmarks_figure.is_on_the_same_row();
                     let on_same_col = pencilmarks_figure.is_on_the_same_col();
 
@@ -64,6 +62,13 @@ impl Method for Pointing {
 
         res
     }
+
+    fn difficulty(&self) -> u32 {
+        match self {
+            Pointing::Pair => 50,
+            Pointing::Triple => 60,
+        }
+    }
 }
 
 #[cfg(test)]
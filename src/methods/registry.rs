@@ -0,0 +1,200 @@
+use crate::grading::Tier;
+
+use super::{
+    aic::Aic,
+    als::{AlmostLockedSets, AlsPattern},
+    box_line_reduction::BoxLineReduction,
+    bug::BugPlusOne,
+    fishes::Fishes,
+    hidden_n::Hidden,
+    medusa::Medusa,
+    naked_n::Naked,
+    pointing_ns::Pointing,
+    simple_coloring::{MultiColoring, SimpleColoring},
+    single_digit_patterns::SingleDigitPatterns,
+    unique_rectangles::UniqueRectangles,
+    wings::Wings,
+    x_cycles::XCycles,
+    xy_chain::XYChain,
+    Method,
+};
+
+/// Every method of the crate, in the sudokuwiki.org order.
+/// [`crate::solver::Solver::all_methods`] and [`crate::grading`] are built from it,
+/// so a new method has to be added only here.
+const METHODS: &[fn() -> Box<dyn Method>] = &[
+    // Original sudokuwiki.org order
+    || Box::new(Naked::Single),
+    //
+    || Box::new(Hidden::Single),
+    //
+    || Box::new(Naked::Pair),
+    || Box::new(Naked::Triple),
+    //
+    || Box::new(Hidden::Pair),
+    || Box::new(Hidden::Triple),
+    //
+    || Box::new(Naked::Quad),
+    || Box::new(Hidden::Quad),
+    //
+    || Box::new(Pointing::Pair),
+    || Box::new(Pointing::Triple),
+    //
+    || Box::new(BoxLineReduction::Pair),
+    || Box::new(BoxLineReduction::Triple),
+    //
+    || Box::new(Fishes::XWing),
+    //
+    || Box::new(SingleDigitPatterns::Skyscraper),
+    || Box::new(SingleDigitPatterns::TwoStringKite),
+    || Box::new(SingleDigitPatterns::EmptyRectangle),
+    //
+    || Box::new(SimpleColoring {}),
+    || Box::new(MultiColoring),
+    //
+    || Box::new(Wings::YWing),
+    //
+    || Box::new(Fishes::Swordfish),
+    //
+    || Box::new(Wings::XYZWing),
+    //
+    || Box::new(XCycles),
+    //
+    || Box::new(Wings::WXYZWing),
+    //
+    || Box::new(BugPlusOne),
+    //
+    || Box::new(XYChain::new()),
+    //
+    || Box::new(Medusa),
+    //
+    || Box::new(Fishes::Jellyfish),
+    //
+    || Box::new(UniqueRectangles::Type1),
+    || Box::new(UniqueRectangles::Type2),
+    || Box::new(UniqueRectangles::Type3),
+    || Box::new(UniqueRectangles::Type4),
+    || Box::new(UniqueRectangles::Type5),
+    || Box::new(UniqueRectangles::Type6),
+    || Box::new(UniqueRectangles::Hidden),
+    //
+    || Box::new(Fishes::FinnedXWing),
+    || Box::new(Fishes::SashimiXWing),
+    || Box::new(Fishes::FinnedSwordfish),
+    || Box::new(Fishes::SashimiSwordfish),
+    || Box::new(Fishes::FinnedJellyfish),
+    || Box::new(Fishes::SashimiJellyfish),
+    //
+    || Box::new(Aic::new()),
+    //
+    || Box::new(AlmostLockedSets::new(AlsPattern::XZ)),
+    || Box::new(AlmostLockedSets::new(AlsPattern::XYWing)),
+    || Box::new(AlmostLockedSets::new(AlsPattern::DeathBlossom)),
+];
+
+/// Method from the registry.
+/// Everything except the order is taken from the method itself (once, when it is listed),
+/// so it can't get out of sync.
+#[derive(Clone, Debug)]
+pub struct RegisteredMethod {
+    order: usize,
+    constructor: fn() -> Box<dyn Method>,
+    name: String,
+    difficulty: u32,
+    is_uniqueness_based: bool,
+}
+
+impl RegisteredMethod {
+    /// Position of the method in the sudokuwiki.org order.
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Creates new instance of the method.
+    pub fn create(&self) -> Box<dyn Method> {
+        (self.constructor)()
+    }
+
+    /// Name of the method (its `Display`).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn difficulty(&self) -> u32 {
+        self.difficulty
+    }
+
+    pub fn tier(&self) -> Tier {
+        Tier::from_difficulty(self.difficulty())
+    }
+
+    /// See [`Method::is_uniqueness_based`].
+    pub fn is_uniqueness_based(&self) -> bool {
+        self.is_uniqueness_based
+    }
+}
+
+/// Returns every registered method (in order).
+pub fn all() -> impl Iterator<Item = RegisteredMethod> {
+    METHODS.iter().enumerate().map(|(order, constructor)| {
+        let method = constructor();
+
+        RegisteredMethod {
+            order,
+            constructor: *constructor,
+            name: method.to_string(),
+            difficulty: method.difficulty(),
+            is_uniqueness_based: method.is_uniqueness_based(),
+        }
+    })
+}
+
+/// Finds registered method by its name.
+pub fn find(name: &str) -> Option<RegisteredMethod> {
+    all().find(|method| method.name() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::solver::Solver;
+
+    use super::*;
+
+    #[test]
+    fn names_are_unique() {
+        let names: HashSet<String> = all().map(|method| method.name().to_string()).collect();
+
+        assert_eq!(names.len(), METHODS.len());
+    }
+
+    #[test]
+    fn solver_uses_every_method() {
+        let solver = Solver::all_methods();
+
+        assert_eq!(solver.methods().len(), METHODS.len());
+
+        for ((method, enabled), registered) in solver.methods().iter().zip(all()) {
+            assert!(enabled);
+            assert_eq!(method.to_string(), registered.name());
+        }
+    }
+
+    #[test]
+    fn find_by_name() {
+        let swordfish = find("Swordfish").unwrap();
+
+        assert_eq!(swordfish.difficulty(), 250);
+        assert_eq!(swordfish.tier(), Tier::Hard);
+        assert!(!swordfish.is_uniqueness_based());
+        assert!(find("Jellyfish").is_some());
+
+        assert!(find("Hidden Unique Rectangle")
+            .unwrap()
+            .is_uniqueness_based());
+
+        assert_eq!(find("Naked Single").unwrap().order(), 0);
+        assert!(find("Unknown").is_none());
+    }
+}
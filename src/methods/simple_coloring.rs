@@ -54,18 +54,18 @@ impl ChainLink {
         let figures = vec![Figure::col(col), Figure::row(row), Figure::sqr(sqr)];
 
         for figure in figures {
-            let info = grid.pencilmarks_info(figure);
+            let info = grid.pencilmarks_positions(figure);
 
-            if let Some(number_positions) = info.get(&self.number) {
+            if let Some(number_positions) = info.get(self.number) {
                 if number_positions.len() == 2 {
                     let new_link_position = number_positions
                         .iter()
-                        .find(|&&pos| pos != self.pos)
+                        .find(|&pos| pos != self.pos)
                         .unwrap();
 
                     let new_link = ChainLink {
                         number: self.number,
-                        pos: *new_link_position,
+                        pos: new_link_position,
                         is_colored: !self.is_colored,
                         info: None,
                     };
@@ -238,12 +238,121 @@ impl Method for SimpleColoring {
 
         res
     }
+
+    fn difficulty(&self) -> u32 {
+        220
+    }
+}
+
+/// Looks across two chains of the same number (A and B, with colors A1, A2 and B1, B2).
+///
+/// - Type 1: if A1 sees B1, then A2 or B2 is true: number can be removed from every cell,
+///   which sees both A2 and B2.
+/// - Type 2: if A1 sees both B1 and B2, then A1 is false: number can be removed from A1.
+#[derive(Clone, Debug)]
+pub struct MultiColoring;
+
+impl MultiColoring {
+    fn color(chain: &[ChainLink], is_colored: bool) -> Figure {
+        chain
+            .iter()
+            .filter(|link| link.is_colored == is_colored)
+            .map(|link| link.pos)
+            .collect()
+    }
+
+    /// Returns cells, which see at least one cell of `a` and at least one cell of `b`.
+    fn seen_by_both(a: &Figure, b: &Figure) -> Figure {
+        let seen_by = |figure: &Figure| {
+            figure.iter().fold(Figure::default(), |seen, pos| {
+                seen + Figure::neighbours(pos)
+            })
+        };
+
+        seen_by(a).intersection(seen_by(b))
+    }
+
+    fn sees(a: &Figure, b: &Figure) -> bool {
+        a.iter()
+            .any(|pos| !Figure::neighbours(pos).intersection(b.clone()).is_empty())
+    }
+
+    fn applications(a: &[ChainLink], b: &[ChainLink], number: u8) -> Vec<Action> {
+        let mut res = vec![];
+
+        let chains_positions: Figure = a.iter().chain(b).map(|link| link.pos).collect();
+
+        for (a_color, b_color) in [(false, false), (false, true), (true, false), (true, true)] {
+            let a1 = Self::color(a, a_color);
+            let a2 = Self::color(a, !a_color);
+            let b1 = Self::color(b, b_color);
+            let b2 = Self::color(b, !b_color);
+
+            if !Self::sees(&a1, &b1) {
+                continue;
+            }
+
+            // Type 1
+            let figure = Self::seen_by_both(&a2, &b2) - chains_positions.clone();
+
+            if !figure.is_empty() {
+                res.push(Action::RemovePencilmarks {
+                    figure,
+                    pencilmarks: vec![number],
+                });
+            }
+
+            // Type 2
+            if Self::sees(&a1, &b2) {
+                res.push(Action::RemovePencilmarks {
+                    figure: a1,
+                    pencilmarks: vec![number],
+                });
+            }
+        }
+
+        res
+    }
+}
+
+impl Display for MultiColoring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Multi-Coloring")
+    }
+}
+
+impl Method for MultiColoring {
+    fn get_all_applications(&self, grid: &Grid) -> BTreeSet<Action> {
+        let mut res = BTreeSet::new();
+
+        for number in 1..=9 {
+            // Chains with one link have only one color, so they can't help
+            let chains: Vec<Vec<ChainLink>> = SimpleColoring
+                .get_chains_for_number(grid, number)
+                .into_iter()
+                .filter(|chain| chain.len() >= 2)
+                .collect();
+
+            for (a, b) in chains.iter().tuple_combinations() {
+                res.extend(Self::applications(a, b, number));
+                res.extend(Self::applications(b, a, number));
+            }
+        }
+
+        res
+    }
+
+    fn difficulty(&self) -> u32 {
+        230
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
+    use crate::methods::test_helpful_method;
+
     use super::*;
 
     fn test(grid: &str, predictions: BTreeSet<Action>) {
@@ -374,4 +483,69 @@ mod tests {
 
         assert_eq!(actions, predictions);
     }
+
+    #[test]
+    fn multi_coloring() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .----------------.----------------.---------------.
+            | 5     49   6   | 249   1    249 | 7   8    3    |
+            | 3     189  129 | 6     89   7   | 4   29   5    |
+            | 7     48   29  | 48    3    5   | 29  6    1    |
+            :----------------+----------------+---------------:
+            | 8     6    49  | 5     29   3   | 1   249  7    |
+            | 49    2    7   | 489   6    1   | 3   5    89   |
+            | 1     3    5   | 7     289  489 | 68  249  2689 |
+            :----------------+----------------+---------------:
+            | 29    7    8   | 1     5    6   | 29  3    4    |
+            | 269   19   13  | 2389  4    289 | 5   7    2689 |
+            | 2469  5    34  | 239   7    289 | 68  1    2689 |
+            '----------------'----------------'---------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            MultiColoring,
+            BTreeSet::from([
+                Action::RemovePencilmarks {
+                    figure: vec![11, 34].into(),
+                    pencilmarks: vec![9],
+                },
+                Action::RemovePencilmarks {
+                    figure: vec![63, 72].into(),
+                    pencilmarks: vec![9],
+                },
+            ]),
+        );
+
+        let grid = Grid::from_candidate_grid(
+            "
+            .---------------.-----------------.--------------------.
+            | 2    789   5  | 4    789   79   | 3     1      6     |
+            | 349  1379  14 | 179  5     6    | 29    8      249   |
+            | 49   18    6  | 18   3     2    | 7     459    459   |
+            :---------------+-----------------+--------------------:
+            | 67   5     3  | 2    79    4    | 1     69     8     |
+            | 78   4     12 | 6    1789  3579 | 259   2359   2359  |
+            | 68   12    9  | 58   18    35   | 4     2356   7     |
+            :---------------+-----------------+--------------------:
+            | 1    269   8  | 3    4     579  | 2569  2579   259   |
+            | 5    2369  24 | 79   26    8    | 269   3479   1     |
+            | 349  2369  7  | 59   26    1    | 8     23459  23459 |
+            '---------------'-----------------'--------------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            MultiColoring,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![79].into(),
+                pencilmarks: vec![2],
+            }]),
+        );
+    }
 }
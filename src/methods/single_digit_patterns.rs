@@ -0,0 +1,395 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use itertools::Itertools;
+
+use crate::{action::Action, figure::Figure, grid::Grid};
+
+use super::Method;
+
+/// Patterns of strong links on one number.
+/// Strong link is a row or col, where the number has only two positions.
+#[derive(Clone, Debug)]
+pub enum SingleDigitPatterns {
+    /// Two strong links in parallel lines, where one end of each link is on the same line
+    /// (base). Base ends see each other, so one of the other ends (tops) is true:
+    /// number can be removed from cells, which see both tops.
+    Skyscraper,
+    /// Strong link in a row and strong link in a col, with one end of each in the same sqr.
+    /// One of the other ends is true: number can be removed from cells, which see both of them.
+    TwoStringKite,
+    /// Sqr, where every position of the number is on one row or one col (cross),
+    /// and strong link, one end of which is on one of the cross's lines.
+    /// If the other end is false, the cross's line is false in the sqr, so the other cross's
+    /// line is true: number can be removed from the cell, which sees the other end
+    /// and is on the other cross's line.
+    EmptyRectangle,
+}
+
+/// Returns strong links on the number in the given figures (at most one per figure).
+fn strong_links(grid: &Grid, number: u8, figures: Vec<Figure>) -> Vec<(usize, usize)> {
+    figures
+        .into_iter()
+        .filter_map(|figure| {
+            grid.pencilmarks_positions(figure)
+                .get(number)
+                .and_then(|positions| positions.iter().collect_tuple())
+        })
+        .collect()
+}
+
+/// Returns link in both directions.
+fn directions(&(a, b): &(usize, usize)) -> [(usize, usize); 2] {
+    [(a, b), (b, a)]
+}
+
+fn rows() -> Vec<Figure> {
+    (0..9).map(Figure::row).collect()
+}
+
+fn cols() -> Vec<Figure> {
+    (0..9).map(Figure::col).collect()
+}
+
+/// Returns cells, which see both `a` and `b` (excluding `a` and `b`).
+fn seen_by_both(a: usize, b: usize) -> Figure {
+    Figure::neighbours(a).intersection(Figure::neighbours(b)) - vec![a, b].into()
+}
+
+impl SingleDigitPatterns {
+    fn skyscraper(grid: &Grid, number: u8) -> Vec<Figure> {
+        let mut res = vec![];
+
+        for rotated in [false, true] {
+            let (lines, line_of): (Vec<Figure>, fn(usize) -> u8) = if rotated {
+                (cols(), Figure::row_of)
+            } else {
+                (rows(), Figure::col_of)
+            };
+
+            let links = strong_links(grid, number, lines);
+
+            for (link1, link2) in links.iter().tuple_combinations() {
+                for ((base1, top1), (base2, top2)) in directions(link1)
+                    .into_iter()
+                    .cartesian_product(directions(link2))
+                {
+                    // If tops are on the same line too, it is X-Wing
+                    if line_of(base1) == line_of(base2) && line_of(top1) != line_of(top2) {
+                        res.push(seen_by_both(top1, top2));
+                    }
+                }
+            }
+        }
+
+        res
+    }
+
+    fn two_string_kite(grid: &Grid, number: u8) -> Vec<Figure> {
+        let mut res = vec![];
+
+        let row_links = strong_links(grid, number, rows());
+        let col_links = strong_links(grid, number, cols());
+
+        for ((row_base, row_top), (col_base, col_top)) in row_links
+            .iter()
+            .flat_map(directions)
+            .cartesian_product(col_links.iter().flat_map(directions))
+        {
+            let sqr = Figure::sqr_of(row_base);
+
+            if row_base != col_base
+                && Figure::sqr_of(col_base) == sqr
+                && Figure::sqr_of(row_top) != sqr
+                && Figure::sqr_of(col_top) != sqr
+            {
+                res.push(seen_by_both(row_top, col_top));
+            }
+        }
+
+        res
+    }
+
+    fn empty_rectangle(grid: &Grid, number: u8) -> Vec<Figure> {
+        let mut res = vec![];
+
+        let row_links = strong_links(grid, number, rows());
+        let col_links = strong_links(grid, number, cols());
+
+        for sqr in 0..9 {
+            let Some(positions) = grid
+                .pencilmarks_positions(Figure::sqr(sqr))
+                .get(number)
+                .cloned()
+            else {
+                continue;
+            };
+
+            if positions.is_on_the_same_row().is_some() || positions.is_on_the_same_col().is_some()
+            {
+                continue;
+            }
+
+            let sqr_rows = sqr / 3 * 3..sqr / 3 * 3 + 3;
+            let sqr_cols = sqr % 3 * 3..sqr % 3 * 3 + 3;
+
+            for (row, col) in sqr_rows.clone().cartesian_product(sqr_cols.clone()) {
+                let cross = Figure::row(row) + Figure::col(col);
+
+                if !positions.difference(cross).is_empty() {
+                    continue;
+                }
+
+                // Link in a col, one end of which is on the cross's row
+                for (on_cross, other) in col_links.iter().flat_map(directions) {
+                    if Figure::row_of(on_cross) == row
+                        && !sqr_cols.contains(&Figure::col_of(on_cross))
+                        && !sqr_rows.contains(&Figure::row_of(other))
+                    {
+                        res.push(vec![Figure::row_of(other) as usize * 9 + col as usize].into());
+                    }
+                }
+
+                // Link in a row, one end of which is on the cross's col
+                for (on_cross, other) in row_links.iter().flat_map(directions) {
+                    if Figure::col_of(on_cross) == col
+                        && !sqr_rows.contains(&Figure::row_of(on_cross))
+                        && !sqr_cols.contains(&Figure::col_of(other))
+                    {
+                        res.push(vec![row as usize * 9 + Figure::col_of(other) as usize].into());
+                    }
+                }
+            }
+        }
+
+        res
+    }
+}
+
+impl Display for SingleDigitPatterns {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                SingleDigitPatterns::Skyscraper => "Skyscraper",
+                SingleDigitPatterns::TwoStringKite => "Two-String Kite",
+                SingleDigitPatterns::EmptyRectangle => "Empty Rectangle",
+            }
+        )
+    }
+}
+
+impl Method for SingleDigitPatterns {
+    fn get_all_applications(&self, grid: &Grid) -> BTreeSet<Action> {
+        let mut res = BTreeSet::new();
+
+        for number in 1..=9 {
+            let figures = match self {
+                SingleDigitPatterns::Skyscraper => Self::skyscraper(grid, number),
+                SingleDigitPatterns::TwoStringKite => Self::two_string_kite(grid, number),
+                SingleDigitPatterns::EmptyRectangle => Self::empty_rectangle(grid, number),
+            };
+
+            res.extend(
+                figures
+                    .into_iter()
+                    .filter(|figure| !figure.is_empty())
+                    .map(|figure| Action::RemovePencilmarks {
+                        figure,
+                        pencilmarks: vec![number],
+                    }),
+            );
+        }
+
+        res
+    }
+
+    fn difficulty(&self) -> u32 {
+        match self {
+            SingleDigitPatterns::Skyscraper => 205,
+            SingleDigitPatterns::TwoStringKite => 210,
+            SingleDigitPatterns::EmptyRectangle => 215,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::methods::test_helpful_method;
+
+    use super::*;
+
+    fn removals(removals: &[(Vec<usize>, u8)]) -> BTreeSet<Action> {
+        removals
+            .iter()
+            .map(|(figure, number)| Action::RemovePencilmarks {
+                figure: figure.clone().into(),
+                pencilmarks: vec![*number],
+            })
+            .collect()
+    }
+
+    #[test]
+    fn skyscraper() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .-----------.------------.---------------.
+            | 1  37  46 | 47   2  5  | 367   8   9   |
+            | 2  9   35 | 178  6  18 | 4     17  35  |
+            | 8  57  46 | 14   9  3  | 26    12  57  |
+            :-----------+------------+---------------:
+            | 3  6   1  | 9    5  2  | 78    4   78  |
+            | 5  4   7  | 18   3  18 | 9     6   2   |
+            | 9  2   8  | 6    7  4  | 5     3   1   |
+            :-----------+------------+---------------:
+            | 7  8   2  | 3    4  9  | 1     5   6   |
+            | 4  35  9  | 25   1  6  | 2378  27  378 |
+            | 6  1   35 | 25   8  7  | 23    9   4   |
+            '-----------'------------'---------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            SingleDigitPatterns::Skyscraper,
+            removals(&[
+                (vec![6, 11], 3),
+                (vec![6, 71], 3),
+                (vec![11, 64], 3),
+                (vec![64, 78], 3),
+            ]),
+        );
+
+        let grid = Grid::from_candidate_grid(
+            "
+            .-----------.-----------------.---------------------.
+            | 8   56  3 | 479   245  2567 | 12457  124567  1279 |
+            | 27  56  1 | 479   3    2567 | 8      24567   279  |
+            | 27  4   9 | 1     58   568  | 35     356     27   |
+            :-----------+-----------------+---------------------:
+            | 3   7   2 | 6     15   15   | 9      8       4    |
+            | 6   9   4 | 38    7    38   | 12     12      5    |
+            | 5   1   8 | 2     9    4    | 37     37      6    |
+            :-----------+-----------------+---------------------:
+            | 4   8   6 | 5     12   127  | 17     9       3    |
+            | 1   3   7 | 48    6    9    | 245    245     28   |
+            | 9   2   5 | 3478  148  1378 | 6      147     178  |
+            '-----------'-----------------'---------------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            SingleDigitPatterns::Skyscraper,
+            removals(&[(vec![66, 79], 4)]),
+        );
+    }
+
+    #[test]
+    fn two_string_kite() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .---------------------.-------------------.-------------------.
+            | 157     3    2      | 4      179   6    | 579  8     79     |
+            | 48      48   6      | 3579   379   579  | 379  2     1      |
+            | 157     59   1579   | 8      137   2    | 357  46    46     |
+            :---------------------+-------------------+-------------------:
+            | 234578  1    345789 | 2379   6     4789 | 79   3459  23479  |
+            | 6       459  34579  | 12379  3479  1479 | 8    3459  23479  |
+            | 23478   489  34789  | 2379   5     4789 | 1    3469  234679 |
+            :---------------------+-------------------+-------------------:
+            | 9       2    134    | 17     8     147  | 6    13    5      |
+            | 138     6    138    | 159    2     159  | 4    7     39     |
+            | 145     7    145    | 6      49    3    | 2    19    8      |
+            '---------------------'-------------------'-------------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            SingleDigitPatterns::TwoStringKite,
+            removals(&[(vec![38], 4)]),
+        );
+
+        let grid = Grid::from_candidate_grid(
+            "
+            .----------.------------------.------------.
+            | 2   1  9 | 3457  347   357  | 35   6   8 |
+            | 8   6  3 | 25    12    9    | 15   4   7 |
+            | 7   4  5 | 6     8     13   | 2    13  9 |
+            :----------+------------------+------------:
+            | 1   2  8 | 357   367   3567 | 37   9   4 |
+            | 5   9  6 | 237   237   4    | 8    37  1 |
+            | 3   7  4 | 1     9     8    | 6    5   2 |
+            :----------+------------------+------------:
+            | 46  8  1 | 479   5     267  | 79   27  3 |
+            | 46  3  2 | 479   1467  167  | 179  8   5 |
+            | 9   5  7 | 8     13    123  | 4    12  6 |
+            '----------'------------------'------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            SingleDigitPatterns::TwoStringKite,
+            removals(&[(vec![32], 3), (vec![68], 1), (vec![76], 1)]),
+        );
+    }
+
+    #[test]
+    fn empty_rectangle() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .-------------.------------------.-------------------.
+            | 8   15   67 | 67    3      9   | 145    2     145  |
+            | 26  9    17 | 4     57     26  | 15     8     3    |
+            | 25  3    4  | 258   58     1   | 9      6     7    |
+            :-------------+------------------+-------------------:
+            | 16  8    9  | 3     45     456 | 2      7     15   |
+            | 3   7    5  | 1     2      8   | 6      49    49   |
+            | 4   2    16 | 56    9      7   | 3      15    8    |
+            :-------------+------------------+-------------------:
+            | 9   145  3  | 2578  14578  245 | 14578  145   6    |
+            | 15  6    8  | 9     1457   45  | 1457   3     2    |
+            | 7   145  2  | 58    6      3   | 1458   1459  1459 |
+            '-------------'------------------'-------------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            SingleDigitPatterns::EmptyRectangle,
+            removals(&[(vec![67], 5)]),
+        );
+
+        let grid = Grid::from_candidate_grid(
+            "
+            .------------------.----------------.---------------.
+            | 168  3      468  | 9    168   58  | 456  7    2   |
+            | 7    69     5    | 4    2     3   | 8    69   1   |
+            | 2    1689   4689 | 168  678   578 | 456  569  3   |
+            :------------------+----------------+---------------:
+            | 19   4      79   | 5    17    6   | 2    3    8   |
+            | 5    28     3    | 28   4     9   | 7    1    6   |
+            | 68   1268   2678 | 128  3     78  | 9    45   45  |
+            :------------------+----------------+---------------:
+            | 689  25689  2689 | 7    569   1   | 3    456  459 |
+            | 4    569    69   | 3    569   2   | 1    8    7   |
+            | 3    7      1    | 68   5689  4   | 56   2    59  |
+            '------------------'----------------'---------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            SingleDigitPatterns::EmptyRectangle,
+            removals(&[(vec![25], 6)]),
+        );
+    }
+}
@@ -0,0 +1,633 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use itertools::Itertools;
+
+use crate::{action::Action, cell::Cell, figure::Figure, grid::Grid, pencilmarks::Pencilmarks};
+
+use super::Method;
+
+/// Four cells in two rows, two cols and two sqrs, which all have pencilmarks A and B.
+/// If all of them had only A and B, the puzzle would have two solutions (deadly pattern).
+/// So, if the puzzle has unique solution, some of the extra pencilmarks must be true.
+#[derive(Clone, Debug)]
+struct Rectangle {
+    /// Corners in order: top left, top right, bottom left, bottom right.
+    /// So the diagonal of the corner `i` is `3 - i`.
+    corners: [usize; 4],
+    pencilmarks: [Pencilmarks; 4],
+    /// A and B
+    pair: Pencilmarks,
+}
+
+impl Rectangle {
+    /// Returns every rectangle of the grid, once for every pair of its common pencilmarks.
+    fn all(grid: &Grid) -> Vec<Rectangle> {
+        let mut res = vec![];
+
+        for ((r1, r2), (c1, c2)) in (0..9)
+            .tuple_combinations()
+            .cartesian_product((0..9).tuple_combinations())
+        {
+            // Rectangle must be in exactly two sqrs
+            if (r1 / 3 == r2 / 3) == (c1 / 3 == c2 / 3) {
+                continue;
+            }
+
+            let corners = [r1 * 9 + c1, r1 * 9 + c2, r2 * 9 + c1, r2 * 9 + c2];
+
+            let Some(pencilmarks) = corners
+                .iter()
+                .map(|&pos| match grid[pos] {
+                    Cell::Pencilmarks(p) => Some(p),
+                    Cell::Number(_) => None,
+                })
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+
+            let pencilmarks: [Pencilmarks; 4] = pencilmarks.try_into().unwrap();
+            let common = pencilmarks
+                .iter()
+                .fold(Pencilmarks::all(), |common, p| common.intersection(*p));
+
+            for (a, b) in common.iter().tuple_combinations() {
+                res.push(Rectangle {
+                    corners,
+                    pencilmarks,
+                    pair: vec![a, b].into(),
+                });
+            }
+        }
+
+        res
+    }
+
+    /// Returns indices of the corners with only A and B.
+    fn bivalue(&self) -> Vec<usize> {
+        (0..4).filter(|&i| self.extras(i).is_empty()).collect()
+    }
+
+    /// Returns indices of the corners with more pencilmarks than A and B.
+    fn roof(&self) -> Vec<usize> {
+        (0..4).filter(|&i| !self.extras(i).is_empty()).collect()
+    }
+
+    fn extras(&self, corner: usize) -> Pencilmarks {
+        self.pencilmarks[corner] - self.pair
+    }
+
+    fn is_diagonal(a: usize, b: usize) -> bool {
+        a + b == 3
+    }
+
+    fn figure(&self) -> Figure {
+        self.corners.to_vec().into()
+    }
+
+    /// Returns rows and cols (and sqr, if any), which contain both given corners.
+    fn common_units(&self, a: usize, b: usize) -> Vec<Figure> {
+        let (a, b) = (self.corners[a], self.corners[b]);
+
+        [
+            Figure::row(Figure::row_of(a)),
+            Figure::col(Figure::col_of(a)),
+            Figure::sqr(Figure::sqr_of(a)),
+        ]
+        .into_iter()
+        .filter(|unit| unit.contains(b))
+        .collect()
+    }
+
+    /// Returns cells, which see every given corner (excluding corners themselves).
+    fn seen_by_all(&self, corners: &[usize]) -> Figure {
+        corners.iter().fold(Figure::all_cells(), |figure, &i| {
+            figure.intersection(Figure::neighbours(self.corners[i]))
+        }) - self.figure()
+    }
+}
+
+/// Returns positions of the number in the figure.
+fn positions_of(grid: &Grid, figure: Figure, number: u8) -> Figure {
+    grid.pencilmarks_positions(figure)
+        .get(number)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Unique Rectangles, as described on sudokuwiki.org.
+/// Every type assumes, that the puzzle has unique solution.
+#[derive(Clone, Debug)]
+pub enum UniqueRectangles {
+    /// Three corners have only AB: A and B can be removed from the fourth corner.
+    Type1,
+    /// Two corners on one side have only AB, two others have AB and the same extra C:
+    /// C can be removed from cells, seeing both of them.
+    Type2,
+    /// Two corners on one side have only AB, extras of two others form naked subset
+    /// with other cells of their unit.
+    Type3,
+    /// Two corners on one side have only AB, and A is only in two others in their unit:
+    /// B can be removed from them.
+    Type4,
+    /// Two or three corners (not on one side) have AB and the same extra C:
+    /// C can be removed from cells, seeing all of them.
+    Type5,
+    /// Two diagonal corners have only AB, and A forms X-Wing on the rectangle:
+    /// A can be removed from two other corners.
+    Type6,
+    /// One corner has only AB, and A is only in the rectangle on both the row and the col of
+    /// the opposite corner: B can be removed from the opposite corner.
+    Hidden,
+}
+
+impl UniqueRectangles {
+    fn type1(rectangle: &Rectangle) -> Vec<Action> {
+        let roof = rectangle.roof();
+
+        if roof.len() != 1 {
+            return vec![];
+        }
+
+        vec![Action::RemovePencilmarks {
+            figure: vec![rectangle.corners[roof[0]]].into(),
+            pencilmarks: rectangle.pair.to_vec(),
+        }]
+    }
+
+    /// Returns the only extra pencilmark of the roof corners, if it is the same for all of them.
+    fn common_extra(rectangle: &Rectangle, roof: &[usize]) -> Option<u8> {
+        let extras = rectangle.extras(roof[0]);
+
+        (extras.len() == 1 && roof.iter().all(|&i| rectangle.extras(i) == extras))
+            .then(|| extras.iter().next().unwrap())
+    }
+
+    fn type2(rectangle: &Rectangle) -> Vec<Action> {
+        let roof = rectangle.roof();
+
+        if roof.len() != 2 || Rectangle::is_diagonal(roof[0], roof[1]) {
+            return vec![];
+        }
+
+        Self::common_extra(rectangle, &roof)
+            .map(|extra| Action::RemovePencilmarks {
+                figure: rectangle.seen_by_all(&roof),
+                pencilmarks: vec![extra],
+            })
+            .into_iter()
+            .collect()
+    }
+
+    fn type3(grid: &Grid, rectangle: &Rectangle) -> Vec<Action> {
+        let roof = rectangle.roof();
+
+        if roof.len() != 2 || Rectangle::is_diagonal(roof[0], roof[1]) {
+            return vec![];
+        }
+
+        let extras = rectangle.extras(roof[0]) + rectangle.extras(roof[1]);
+        let roof_figure: Figure = roof.iter().map(|&i| rectangle.corners[i]).collect();
+
+        let mut res = vec![];
+
+        for unit in rectangle.common_units(roof[0], roof[1]) {
+            let others: Vec<(usize, Pencilmarks)> = (unit.clone() - roof_figure.clone())
+                .into_iter()
+                .filter_map(|pos| match grid[pos] {
+                    Cell::Pencilmarks(p) => Some((pos, p)),
+                    Cell::Number(_) => None,
+                })
+                .collect();
+
+            // Roof corners act as one cell with extras as pencilmarks
+            for size in 1..=3 {
+                for subset in others.iter().combinations(size) {
+                    let pencilmarks = subset.iter().fold(extras, |union, (_, p)| union + *p);
+
+                    if pencilmarks.len() != size + 1 {
+                        continue;
+                    }
+
+                    let subset_figure: Figure = subset.iter().map(|(pos, _)| *pos).collect();
+
+                    res.push(Action::RemovePencilmarks {
+                        figure: unit.clone() - roof_figure.clone() - subset_figure,
+                        pencilmarks: pencilmarks.to_vec(),
+                    });
+                }
+            }
+        }
+
+        res
+    }
+
+    fn type4(grid: &Grid, rectangle: &Rectangle) -> Vec<Action> {
+        let roof = rectangle.roof();
+
+        if roof.len() != 2 || Rectangle::is_diagonal(roof[0], roof[1]) {
+            return vec![];
+        }
+
+        let roof_figure: Figure = roof.iter().map(|&i| rectangle.corners[i]).collect();
+
+        let mut res = vec![];
+
+        for unit in rectangle.common_units(roof[0], roof[1]) {
+            for (a, b) in rectangle.pair.iter().tuple_combinations() {
+                for (locked, removed) in [(a, b), (b, a)] {
+                    if positions_of(grid, unit.clone(), locked) == roof_figure {
+                        res.push(Action::RemovePencilmarks {
+                            figure: roof_figure.clone(),
+                            pencilmarks: vec![removed],
+                        });
+                    }
+                }
+            }
+        }
+
+        res
+    }
+
+    fn type5(rectangle: &Rectangle) -> Vec<Action> {
+        let roof = rectangle.roof();
+
+        let is_type5 = match roof.len() {
+            2 => Rectangle::is_diagonal(roof[0], roof[1]),
+            3 => true,
+            _ => false,
+        };
+
+        if !is_type5 {
+            return vec![];
+        }
+
+        Self::common_extra(rectangle, &roof)
+            .map(|extra| Action::RemovePencilmarks {
+                figure: rectangle.seen_by_all(&roof),
+                pencilmarks: vec![extra],
+            })
+            .into_iter()
+            .collect()
+    }
+
+    fn type6(grid: &Grid, rectangle: &Rectangle) -> Vec<Action> {
+        let bivalue = rectangle.bivalue();
+
+        if bivalue.len() != 2 || !Rectangle::is_diagonal(bivalue[0], bivalue[1]) {
+            return vec![];
+        }
+
+        let roof: Figure = rectangle
+            .roof()
+            .iter()
+            .map(|&i| rectangle.corners[i])
+            .collect();
+
+        let [top_left, _, _, bottom_right] = rectangle.corners;
+        let lines = [
+            Figure::row(Figure::row_of(top_left)),
+            Figure::row(Figure::row_of(bottom_right)),
+            Figure::col(Figure::col_of(top_left)),
+            Figure::col(Figure::col_of(bottom_right)),
+        ];
+
+        rectangle
+            .pair
+            .iter()
+            .filter(|&number| {
+                lines.iter().all(|line| {
+                    (positions_of(grid, line.clone(), number) - rectangle.figure()).is_empty()
+                })
+            })
+            .map(|number| Action::RemovePencilmarks {
+                figure: roof.clone(),
+                pencilmarks: vec![number],
+            })
+            .collect()
+    }
+
+    fn hidden(grid: &Grid, rectangle: &Rectangle) -> Vec<Action> {
+        let mut res = vec![];
+
+        for floor in rectangle.bivalue() {
+            let opposite = 3 - floor;
+            let pos = rectangle.corners[opposite];
+
+            if rectangle.extras(opposite).is_empty() {
+                continue;
+            }
+
+            let lines = [
+                Figure::row(Figure::row_of(pos)),
+                Figure::col(Figure::col_of(pos)),
+            ];
+
+            for (a, b) in rectangle.pair.iter().tuple_combinations() {
+                for (locked, removed) in [(a, b), (b, a)] {
+                    let is_locked = lines.iter().all(|line| {
+                        (positions_of(grid, line.clone(), locked) - rectangle.figure()).is_empty()
+                    });
+
+                    if is_locked {
+                        res.push(Action::RemovePencilmarks {
+                            figure: vec![pos].into(),
+                            pencilmarks: vec![removed],
+                        });
+                    }
+                }
+            }
+        }
+
+        res
+    }
+}
+
+impl Display for UniqueRectangles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UniqueRectangles::Type1 => write!(f, "Unique Rectangle Type 1"),
+            UniqueRectangles::Type2 => write!(f, "Unique Rectangle Type 2"),
+            UniqueRectangles::Type3 => write!(f, "Unique Rectangle Type 3"),
+            UniqueRectangles::Type4 => write!(f, "Unique Rectangle Type 4"),
+            UniqueRectangles::Type5 => write!(f, "Unique Rectangle Type 5"),
+            UniqueRectangles::Type6 => write!(f, "Unique Rectangle Type 6"),
+            UniqueRectangles::Hidden => write!(f, "Hidden Unique Rectangle"),
+        }
+    }
+}
+
+impl Method for UniqueRectangles {
+    fn get_all_applications(&self, grid: &Grid) -> BTreeSet<Action> {
+        let mut res = BTreeSet::new();
+
+        for rectangle in Rectangle::all(grid) {
+            let actions = match self {
+                UniqueRectangles::Type1 => Self::type1(&rectangle),
+                UniqueRectangles::Type2 => Self::type2(&rectangle),
+                UniqueRectangles::Type3 => Self::type3(grid, &rectangle),
+                UniqueRectangles::Type4 => Self::type4(grid, &rectangle),
+                UniqueRectangles::Type5 => Self::type5(&rectangle),
+                UniqueRectangles::Type6 => Self::type6(grid, &rectangle),
+                UniqueRectangles::Hidden => Self::hidden(grid, &rectangle),
+            };
+
+            res.extend(actions.into_iter().filter(|action| match action {
+                Action::RemovePencilmarks { figure, .. } => !figure.is_empty(),
+                _ => true,
+            }));
+        }
+
+        res
+    }
+
+    fn difficulty(&self) -> u32 {
+        match self {
+            UniqueRectangles::Type1 => 280,
+            UniqueRectangles::Type2 => 300,
+            UniqueRectangles::Type3 => 320,
+            UniqueRectangles::Type4 => 310,
+            UniqueRectangles::Type5 => 320,
+            UniqueRectangles::Type6 => 330,
+            UniqueRectangles::Hidden => 330,
+        }
+    }
+
+    fn is_uniqueness_based(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{methods::test_helpful_method, solver::Solver};
+
+    use super::*;
+
+    fn test(grid: &str, method: UniqueRectangles, valid_candidates: BTreeSet<Action>) {
+        let grid = Grid::from_candidate_grid(grid).unwrap();
+
+        test_helpful_method(&grid, method, valid_candidates);
+    }
+
+    #[test]
+    fn type1() {
+        test(
+            "
+            .--------------.--------------.---------------------.
+            | 7   8   5    | 9   234  6   | 24     1      234   |
+            | 4   29  3    | 27  8    1   | 25679  25679  257   |
+            | 16  29  16   | 5   237  34  | 2479   8      2347  |
+            :--------------+--------------+---------------------:
+            | 59  34  28   | 6   1    349 | 24578  257    24578 |
+            | 16  34  1268 | 23  5    7   | 12468  26     9     |
+            | 59  7   126  | 8   24   49  | 3      256    1245  |
+            :--------------+--------------+---------------------:
+            | 2   15  7    | 13  39   8   | 159    4      6     |
+            | 8   16  9    | 4   67   5   | 127    3      127   |
+            | 3   56  4    | 17  679  2   | 15789  579    1578  |
+            '--------------'--------------'---------------------'
+            ",
+            UniqueRectangles::Type1,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![38].into(),
+                pencilmarks: vec![1, 6],
+            }]),
+        );
+    }
+
+    #[test]
+    fn type2() {
+        test(
+            "
+            .------------------.----------.-----------------.
+            | 1     5     9    | 2  6   4 | 3    7     8    |
+            | 2     36    36   | 8  7   9 | 4    1     5    |
+            | 78    4     78   | 1  3   5 | 9    26    26   |
+            :------------------+----------+-----------------:
+            | 3689  369   368  | 7  5   1 | 268  4     2369 |
+            | 569   2     156  | 4  8   3 | 7    69    169  |
+            | 378   137   4    | 6  9   2 | 5    38    13   |
+            :------------------+----------+-----------------:
+            | 5679  1679  1567 | 3  12  8 | 126  256   4    |
+            | 35    13    2    | 9  4   6 | 18   358   7    |
+            | 4     8     136  | 5  12  7 | 126  2369  2369 |
+            '------------------'----------'-----------------'
+            ",
+            UniqueRectangles::Type2,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![33, 61, 79, 80].into(),
+                pencilmarks: vec![6],
+            }]),
+        );
+    }
+
+    #[test]
+    fn type3() {
+        test(
+            "
+            .------------------.-----------------.---------------.
+            | 2357  2378  6    | 28   1257  4578 | 145   9   34  |
+            | 2357  237   1    | 9    2567  4567 | 456   8   346 |
+            | 4     89    59   | 68   16    3    | 156   7   2   |
+            :------------------+-----------------+---------------:
+            | 27    6     27   | 1    4     9    | 3     5   8   |
+            | 19    139   39   | 5    8     2    | 467   46  467 |
+            | 8     5     4    | 36   367   67   | 9     2   1   |
+            :------------------+-----------------+---------------:
+            | 235   234   8    | 7    2356  1    | 246   46  9   |
+            | 19    1279  279  | 4    26    68   | 2678  3   5   |
+            | 6     2347  2357 | 238  9     58   | 2478  1   47  |
+            '------------------'-----------------'---------------'
+            ",
+            UniqueRectangles::Type3,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![1].into(),
+                pencilmarks: vec![2, 3, 4, 7],
+            }]),
+        );
+    }
+
+    #[test]
+    fn type4() {
+        test(
+            "
+            .-------------.-----------------.-----------.
+            | 15   9  7   | 8   15     4    | 6  3   2  |
+            | 126  4  126 | 69  12369  1236 | 7  5   8  |
+            | 256  8  3   | 7   256    256  | 9  4   1  |
+            :-------------+-----------------+-----------:
+            | 3    6  28  | 4   28     7    | 5  1   9  |
+            | 4    5  9   | 1   36     36   | 2  8   7  |
+            | 128  7  12  | 59  259    258  | 4  6   3  |
+            :-------------+-----------------+-----------:
+            | 7    1  568 | 56  4568   9    | 3  2   46 |
+            | 69   2  4   | 3   167    16   | 8  79  5  |
+            | 689  3  56  | 2   4567   568  | 1  79  46 |
+            '-------------'-----------------'-----------'
+            ",
+            UniqueRectangles::Type4,
+            BTreeSet::from([
+                Action::RemovePencilmarks {
+                    figure: vec![13, 14].into(),
+                    pencilmarks: vec![6],
+                },
+                Action::RemovePencilmarks {
+                    figure: vec![58, 76].into(),
+                    pencilmarks: vec![6],
+                },
+            ]),
+        );
+    }
+
+    #[test]
+    fn type5() {
+        let mut grid = Grid::from_str(&"0".repeat(81)).unwrap();
+
+        grid.set_pencilmarks(0, vec![1, 2]);
+        grid.set_pencilmarks(3, vec![1, 2, 3]);
+        grid.set_pencilmarks(9, vec![1, 2, 3]);
+        grid.set_pencilmarks(12, vec![1, 2]);
+
+        assert_eq!(
+            UniqueRectangles::Type5.get_all_applications(&grid),
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![1, 2, 13, 14].into(),
+                pencilmarks: vec![3],
+            }])
+        );
+    }
+
+    #[test]
+    fn type6() {
+        test(
+            "
+            .----------------.-------------.----------------.
+            | 4    58     9  | 2  7    3   | 68    1    56  |
+            | 7    28     6  | 4  5    1   | 289   29   3   |
+            | 3    1      25 | 9  8    6   | 24    7    245 |
+            :----------------+-------------+----------------:
+            | 5    369    4  | 8  69   29  | 1     239  7   |
+            | 269  7      1  | 3  469  249 | 29    5    8   |
+            | 29   39     8  | 5  1    7   | 2349  6    24  |
+            :----------------+-------------+----------------:
+            | 1    249    7  | 6  3    489 | 5     248  29  |
+            | 69   45     3  | 1  2    458 | 7     48   69  |
+            | 8    24569  25 | 7  49   459 | 236   234  1   |
+            '----------------'-------------'----------------'
+            ",
+            UniqueRectangles::Type6,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![26, 51].into(),
+                pencilmarks: vec![4],
+            }]),
+        );
+    }
+
+    #[test]
+    fn hidden() {
+        test(
+            "
+            .-----------------.-------------------.---------------.
+            | 1379   6    157 | 3579  4     359   | 137   2    8  |
+            | 13479  379  2   | 6     379   8     | 1347  147  5  |
+            | 3478   357  578 | 357   1     2     | 347   9    6  |
+            :-----------------+-------------------+---------------:
+            | 5      79   78  | 1     389   4     | 2     6    37 |
+            | 789    4    6   | 2     3589  359   | 159   15   37 |
+            | 2      1    3   | 59    6     7     | 589   58   4  |
+            :-----------------+-------------------+---------------:
+            | 6      8    157 | 4     2     15    | 57    3    9  |
+            | 137    357  4   | 3579  3579  13569 | 5678  578  2  |
+            | 37     2    9   | 8     357   356   | 4567  47   1  |
+            '-----------------'-------------------'---------------'
+            ",
+            UniqueRectangles::Hidden,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![69].into(),
+                pencilmarks: vec![5],
+            }]),
+        );
+    }
+
+    #[test]
+    fn turned_off() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .--------------.--------------.---------------------.
+            | 7   8   5    | 9   234  6   | 24     1      234   |
+            | 4   29  3    | 27  8    1   | 25679  25679  257   |
+            | 16  29  16   | 5   237  34  | 2479   8      2347  |
+            :--------------+--------------+---------------------:
+            | 59  34  28   | 6   1    349 | 24578  257    24578 |
+            | 16  34  1268 | 23  5    7   | 12468  26     9     |
+            | 59  7   126  | 8   24   49  | 3      256    1245  |
+            :--------------+--------------+---------------------:
+            | 2   15  7    | 13  39   8   | 159    4      6     |
+            | 8   16  9    | 4   67   5   | 127    3      127   |
+            | 3   56  4    | 17  679  2   | 15789  579    1578  |
+            '--------------'--------------'---------------------'
+            ",
+        )
+        .unwrap();
+        let mut solver = Solver::all_methods();
+
+        // Only Type 1 is enabled, so that the result does not depend on other methods
+        for i in 0..solver.methods().len() {
+            let is_type1 = solver.methods()[i].0.to_string() == "Unique Rectangle Type 1";
+            solver.set_state(i, is_type1);
+        }
+
+        assert!(solver
+            .first_applicable(&grid)
+            .is_some_and(|(method, _)| method.to_string() == "Unique Rectangle Type 1"));
+
+        solver.set_uniqueness_methods(false);
+
+        assert!(solver
+            .first_applicable(&grid)
+            .is_none_or(|(method, _)| !method.is_uniqueness_based()));
+    }
+}
@@ -0,0 +1,269 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use itertools::Itertools;
+
+use crate::{action::Action, cell::Cell, figure::Figure, grid::Grid, pencilmarks::Pencilmarks};
+
+use super::Method;
+
+/// Wing is N cells with N pencilmarks in total, where every pencilmark except one (Z)
+/// is restricted: all cells with it see each other, so it can be placed only once.
+/// Then Z must be in one of the wing's cells, and can be removed from every cell,
+/// which sees all wing's cells with Z.
+#[derive(Clone, Debug)]
+pub enum Wings {
+    /// Pivot XY with pincers XZ and YZ: three bivalue cells.
+    YWing,
+    /// Pivot XYZ with pincers XZ and YZ.
+    XYZWing,
+    /// Any four cells with four pencilmarks.
+    WXYZWing,
+}
+
+impl Wings {
+    fn size(&self) -> usize {
+        match self {
+            Wings::YWing | Wings::XYZWing => 3,
+            Wings::WXYZWing => 4,
+        }
+    }
+
+    /// Maximum number of pencilmarks in one cell of the wing.
+    fn max_cell_size(&self) -> usize {
+        match self {
+            Wings::YWing => 2,
+            Wings::XYZWing | Wings::WXYZWing => self.size(),
+        }
+    }
+
+    /// XYZ-Wing needs pivot: cell with every pencilmark of the wing.
+    /// Without pivot three cells are Y-Wing.
+    fn needs_pivot(&self) -> bool {
+        match self {
+            Wings::XYZWing => true,
+            Wings::YWing | Wings::WXYZWing => false,
+        }
+    }
+
+    /// Recursively chooses cells (in ascending order) with at most `size` pencilmarks in total.
+    fn search(
+        &self,
+        cells: &[(usize, Pencilmarks)],
+        chosen: &mut Vec<(usize, Pencilmarks)>,
+        res: &mut BTreeSet<Action>,
+    ) {
+        let union = chosen
+            .iter()
+            .fold(Pencilmarks::empty(), |union, (_, p)| union + *p);
+
+        if union.len() > self.size() {
+            return;
+        }
+
+        if chosen.len() == self.size() {
+            res.extend(self.application(chosen, union));
+            return;
+        }
+
+        for (i, cell) in cells.iter().enumerate() {
+            chosen.push(*cell);
+            self.search(&cells[i + 1..], chosen, res);
+            chosen.pop();
+        }
+    }
+
+    fn application(&self, wing: &[(usize, Pencilmarks)], union: Pencilmarks) -> Option<Action> {
+        if union.len() != self.size()
+            || (self.needs_pivot() && wing.iter().all(|(_, p)| p.len() != self.size()))
+        {
+            return None;
+        }
+
+        let cells_with = |number: u8| -> Vec<usize> {
+            wing.iter()
+                .filter(|(_, p)| p.contains(&number))
+                .map(|(pos, _)| *pos)
+                .collect()
+        };
+
+        let is_restricted = |cells: &Vec<usize>| -> bool {
+            cells
+                .iter()
+                .tuple_combinations()
+                .all(|(a, b)| Figure::neighbours(*a).contains(*b))
+        };
+
+        let mut not_restricted = union
+            .iter()
+            .filter(|&number| !is_restricted(&cells_with(number)));
+
+        let z = not_restricted.next()?;
+
+        if not_restricted.next().is_some() {
+            return None;
+        }
+
+        let mut figure = Figure::all_cells();
+
+        for pos in cells_with(z) {
+            figure = figure.intersection(Figure::neighbours(pos));
+        }
+
+        figure -= wing.iter().map(|(pos, _)| *pos).collect();
+
+        (!figure.is_empty()).then(|| Action::RemovePencilmarks {
+            figure,
+            pencilmarks: vec![z],
+        })
+    }
+}
+
+impl Display for Wings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Wings::YWing => "Y-Wing",
+                Wings::XYZWing => "XYZ-Wing",
+                Wings::WXYZWing => "WXYZ-Wing",
+            }
+        )
+    }
+}
+
+impl Method for Wings {
+    fn get_all_applications(&self, grid: &Grid) -> BTreeSet<Action> {
+        let mut res = BTreeSet::new();
+
+        let cells: Vec<(usize, Pencilmarks)> = Figure::all_cells()
+            .into_iter()
+            .filter_map(|pos| match grid[pos] {
+                Cell::Pencilmarks(p) if (2..=self.max_cell_size()).contains(&p.len()) => {
+                    Some((pos, p))
+                }
+                _ => None,
+            })
+            .collect();
+
+        self.search(&cells, &mut vec![], &mut res);
+
+        res
+    }
+
+    fn difficulty(&self) -> u32 {
+        match self {
+            Wings::YWing => 240,
+            Wings::XYZWing => 260,
+            Wings::WXYZWing => 320,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::methods::{test_helpful_method, test_method};
+
+    use super::*;
+
+    #[test]
+    fn y_wing() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .--------------.----------------.------------.
+            | 1     4   59 | 59   6    3    | 7  2    8  |
+            | 258   28  6  | 578  578  4    | 9  1    3  |
+            | 89    3   7  | 1    89   2    | 6  4    5  |
+            :--------------+----------------+------------:
+            | 6     1   8  | 3    4    5    | 2  7    9  |
+            | 4     5   2  | 79   79   6    | 8  3    1  |
+            | 7     9   3  | 2    18   18   | 5  6    4  |
+            :--------------+----------------+------------:
+            | 3     7   4  | 58   2    89   | 1  589  6  |
+            | 2589  28  1  | 6    3    789  | 4  589  27 |
+            | 2589  6   59 | 4    158  1789 | 3  589  27 |
+            '--------------'----------------'------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            Wings::YWing,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![12, 76].into(),
+                pencilmarks: vec![8],
+            }]),
+        );
+
+        let grid = Grid::from_candidate_grid(
+            "
+            .-------------.------------------.-------------------.
+            | 8   15   67 | 67    3      9   | 145    2     145  |
+            | 26  9    17 | 4     57     26  | 15     8     3    |
+            | 25  3    4  | 258   58     1   | 9      6     7    |
+            :-------------+------------------+-------------------:
+            | 16  8    9  | 3     45     456 | 2      7     15   |
+            | 3   7    5  | 1     2      8   | 6      49    49   |
+            | 4   2    16 | 56    9      7   | 3      15    8    |
+            :-------------+------------------+-------------------:
+            | 9   145  3  | 2578  14578  245 | 14578  145   6    |
+            | 15  6    8  | 9     1457   45  | 1457   3     2    |
+            | 7   145  2  | 58    6      3   | 1458   1459  1459 |
+            '-------------'------------------'-------------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            Wings::YWing,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![21, 31].into(),
+                pencilmarks: vec![5],
+            }]),
+        );
+    }
+
+    #[test]
+    fn xyz_wing() {
+        test_method(
+            "060000400040002089008007300017024050000010700020050000000009000050000140000400206",
+            Wings::XYZWing,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![62, 80].into(),
+                pencilmarks: vec![3],
+            }]),
+        );
+
+        test_method(
+            "000000000005009670000520040050002030031000900000000000020096400000080001740100002",
+            Wings::XYZWing,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![79, 80].into(),
+                pencilmarks: vec![5],
+            }]),
+        );
+    }
+
+    #[test]
+    fn wxyz_wing() {
+        test_method(
+            "070090600809000000000002003160000007005300000300200148200030080000000051000504000",
+            Wings::WXYZWing,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![34, 52].into(),
+                pencilmarks: vec![2],
+            }]),
+        );
+
+        test_method(
+            "500087060800004007600300020060040300700000008000002000000000200190056000037000006",
+            Wings::WXYZWing,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![18, 19].into(),
+                pencilmarks: vec![1],
+            }]),
+        );
+    }
+}
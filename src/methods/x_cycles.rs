@@ -0,0 +1,293 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use itertools::Itertools;
+
+use crate::{action::Action, figure::Figure, grid::Grid};
+
+use super::Method;
+
+/// Loops longer than this are not searched, so that the search time stays bounded.
+const MAX_LINKS: usize = 12;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Link {
+    /// Number is only in two cells of the unit: one of them must be the number.
+    Strong,
+    /// Cells see each other: only one of them can be the number.
+    Weak,
+}
+
+impl Link {
+    fn next(self) -> Self {
+        match self {
+            Link::Strong => Link::Weak,
+            Link::Weak => Link::Strong,
+        }
+    }
+}
+
+/// Cells with the number and links between them.
+struct Links {
+    number: u8,
+    positions: Figure,
+    /// For every cell: cells, linked to it with strong link.
+    strong: Vec<Figure>,
+}
+
+impl Links {
+    fn new(grid: &Grid, number: u8) -> Self {
+        let mut positions = Figure::default();
+        let mut strong = vec![Figure::default(); 81];
+
+        for unit in Figure::all_figures() {
+            let Some(unit_positions) = grid.pencilmarks_positions(unit).get(number).cloned() else {
+                continue;
+            };
+
+            if let Some((a, b)) = unit_positions.iter().collect_tuple() {
+                strong[a] += vec![b].into();
+                strong[b] += vec![a].into();
+            }
+
+            positions += unit_positions;
+        }
+
+        Self {
+            number,
+            positions,
+            strong,
+        }
+    }
+
+    /// Returns cells, which can be the next in the chain after `pos` with given link.
+    /// Weak link leads only to cells with strong links (or to the start of the loop),
+    /// because next link after it must be strong.
+    fn next(&self, pos: usize, link: Link, start: usize) -> Figure {
+        match link {
+            Link::Strong => self.strong[pos].clone(),
+            Link::Weak => self
+                .positions
+                .intersection(Figure::neighbours(pos))
+                .iter()
+                .filter(|&next| next != pos && (next == start || !self.strong[next].is_empty()))
+                .collect(),
+        }
+    }
+}
+
+/// Nice loops on one number, with alternating strong and weak links.
+/// Strong link can be used as weak.
+///
+/// - Rule 1: continuous loop (even number of links): number can be removed from every other
+///   cell of the units of weak links.
+/// - Rule 2: two strong links meet in a cell: the cell is the number.
+/// - Rule 3: two weak links meet in a cell: the cell can't be the number.
+#[derive(Clone, Debug)]
+pub struct XCycles;
+
+impl XCycles {
+    /// Searches for loops from `start`, with the given type of the first link.
+    fn search(
+        links: &Links,
+        path: &mut Vec<usize>,
+        link: Link,
+        first_link: Link,
+        res: &mut BTreeSet<Action>,
+    ) {
+        let start = path[0];
+        let pos = *path.last().unwrap();
+
+        for next in links.next(pos, link, start) {
+            if next == start {
+                if path.len() >= 3 {
+                    res.extend(Self::loop_application(links, path, first_link, link));
+                }
+                continue;
+            }
+
+            if path.contains(&next) || path.len() == MAX_LINKS {
+                continue;
+            }
+
+            path.push(next);
+            Self::search(links, path, link.next(), first_link, res);
+            path.pop();
+        }
+    }
+
+    fn loop_application(
+        links: &Links,
+        path: &[usize],
+        first_link: Link,
+        last_link: Link,
+    ) -> Option<Action> {
+        let start = path[0];
+
+        match (first_link, last_link) {
+            (Link::Strong, Link::Strong) => Some(Action::PlaceNumber {
+                position: start,
+                number: links.number,
+            }),
+            (Link::Weak, Link::Weak) => Some(Action::RemovePencilmarks {
+                figure: vec![start].into(),
+                pencilmarks: vec![links.number],
+            }),
+            (Link::Strong, Link::Weak) => {
+                let mut figure = Figure::default();
+
+                // Links at odd positions are weak: 1-2, 3-4, ..., (n-1)-0
+                for i in (1..path.len()).step_by(2) {
+                    let a = path[i];
+                    let b = path[(i + 1) % path.len()];
+
+                    for unit in Figure::all_figures() {
+                        if unit.contains(a) && unit.contains(b) {
+                            figure += unit;
+                        }
+                    }
+                }
+
+                figure -= path.iter().copied().collect();
+
+                (!figure.is_empty()).then(|| Action::RemovePencilmarks {
+                    figure,
+                    pencilmarks: vec![links.number],
+                })
+            }
+            // Same loops as (Strong, Weak), but in reversed order
+            (Link::Weak, Link::Strong) => None,
+        }
+    }
+}
+
+impl Display for XCycles {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "X-Cycles")
+    }
+}
+
+impl Method for XCycles {
+    fn get_all_applications(&self, grid: &Grid) -> BTreeSet<Action> {
+        let mut res = BTreeSet::new();
+
+        for number in 1..=9 {
+            let links = Links::new(grid, number);
+
+            for start in links.positions.iter() {
+                for first_link in [Link::Strong, Link::Weak] {
+                    Self::search(&links, &mut vec![start], first_link, first_link, &mut res);
+                }
+            }
+        }
+
+        res
+    }
+
+    fn difficulty(&self) -> u32 {
+        280
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::methods::test_helpful_method;
+
+    use super::*;
+
+    #[test]
+    fn x_cycles() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .-------------.-----------------.-----------------.
+            | 6   7   2   | 3     1    89   | 89     5    4   |
+            | 89  4   3   | 589   2    57   | 789    6    1   |
+            | 89  1   5   | 489   6    47   | 23789  238  289 |
+            :-------------+-----------------+-----------------:
+            | 5   3   489 | 2489  489  6    | 1      28   7   |
+            | 7   26  48  | 1     48   23   | 236    9    5   |
+            | 1   26  89  | 7     5    2389 | 4      38   268 |
+            :-------------+-----------------+-----------------:
+            | 24  9   7   | 2458  3    45   | 268    1    268 |
+            | 3   8   1   | 6     7    29   | 5      4    29  |
+            | 24  5   6   | 2489  489  1    | 289    7    3   |
+            '-------------'-----------------'-----------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            XCycles,
+            BTreeSet::from([
+                Action::PlaceNumber {
+                    position: 71,
+                    number: 9,
+                },
+                Action::RemovePencilmarks {
+                    figure: vec![26].into(),
+                    pencilmarks: vec![9],
+                },
+                Action::RemovePencilmarks {
+                    figure: vec![68].into(),
+                    pencilmarks: vec![9],
+                },
+                Action::RemovePencilmarks {
+                    figure: vec![78].into(),
+                    pencilmarks: vec![9],
+                },
+            ]),
+        );
+
+        let grid = Grid::from_candidate_grid(
+            "
+            .------------.-------------------.--------------------.
+            | 4  237  1  | 678   268   5     | 9     368   68     |
+            | 5  8    6  | 3     9     4     | 12    7     12     |
+            | 9  237  37 | 678   1     278   | 348   5     468    |
+            :------------+-------------------+--------------------:
+            | 8  4    57 | 9     23    123   | 6     123   57     |
+            | 6  17   9  | 18    5     1238  | 1237  4     127    |
+            | 3  15   2  | 4     7     6     | 18    189   1589   |
+            :------------+-------------------+--------------------:
+            | 2  356  4  | 1678  368   13789 | 1578  1689  16789  |
+            | 1  36   38 | 5     3468  3789  | 2478  2689  246789 |
+            | 7  9    58 | 2     468   18    | 1458  168   3      |
+            '------------'-------------------'--------------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            XCycles,
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![61].into(),
+                pencilmarks: vec![1],
+            }]),
+        );
+
+        // Raw grid with a continuous loop
+        let grid = Grid::from_str(
+            "800000000000030800049100000300000980004070005008204006400500003007009000025000600",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            XCycles,
+            [6, 7, 42, 43, 66]
+                .into_iter()
+                .map(|pos| Action::RemovePencilmarks {
+                    figure: vec![pos].into(),
+                    pencilmarks: vec![3],
+                })
+                .chain([Action::RemovePencilmarks {
+                    figure: vec![6, 7, 42, 43].into(),
+                    pencilmarks: vec![3],
+                }])
+                .collect(),
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+use crate::{action::Action, cell::Cell, figure::Figure, grid::Grid, pencilmarks::Pencilmarks};
+
+use super::Method;
+
+/// Returns the other number of the bivalue cell.
+fn other(pencilmarks: Pencilmarks, number: u8) -> u8 {
+    (pencilmarks - vec![number].into()).iter().next().unwrap()
+}
+
+/// Chain of bivalue cells, where every next cell sees the previous one and shares
+/// a number with it. If the first cell is not X, every next cell is forced,
+/// and the last cell becomes X. So either the first or the last cell is X:
+/// X can be removed from every cell, which sees both ends of the chain.
+///
+/// Y-Wing is XY-Chain of three cells.
+#[derive(Clone, Debug)]
+pub struct XYChain {
+    max_length: usize,
+}
+
+impl Default for XYChain {
+    fn default() -> Self {
+        Self { max_length: 10 }
+    }
+}
+
+impl XYChain {
+    /// Returns XY-Chain, which searches chains of up to 10 cells.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum number of cells in the chain.
+    /// Longer chains give more eliminations, but the search time grows fast.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// `chain` is never empty. `forced` is the number, which last cell of the chain becomes,
+    /// if the first cell is not `x`.
+    fn search(
+        &self,
+        cells: &[(usize, Pencilmarks)],
+        chain: &mut Vec<usize>,
+        forced: u8,
+        x: u8,
+        res: &mut BTreeSet<Action>,
+    ) {
+        if chain.len() == self.max_length {
+            return;
+        }
+
+        let last = *chain.last().unwrap();
+
+        for (pos, pencilmarks) in cells {
+            if !pencilmarks.contains(&forced)
+                || !Figure::neighbours(last).contains(*pos)
+                || chain.contains(pos)
+            {
+                continue;
+            }
+
+            let next_forced = other(*pencilmarks, forced);
+
+            chain.push(*pos);
+
+            if next_forced == x && chain.len() >= 3 {
+                let figure = Figure::neighbours(chain[0]).intersection(Figure::neighbours(*pos))
+                    - vec![chain[0], *pos].into();
+
+                if !figure.is_empty() {
+                    res.insert(Action::RemovePencilmarks {
+                        figure,
+                        pencilmarks: vec![x],
+                    });
+                }
+            }
+
+            self.search(cells, chain, next_forced, x, res);
+
+            chain.pop();
+        }
+    }
+}
+
+impl Display for XYChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "XY-Chain")
+    }
+}
+
+impl Method for XYChain {
+    fn get_all_applications(&self, grid: &Grid) -> BTreeSet<Action> {
+        let mut res = BTreeSet::new();
+
+        let cells: Vec<(usize, Pencilmarks)> = Figure::all_cells()
+            .into_iter()
+            .filter_map(|pos| match grid[pos] {
+                Cell::Pencilmarks(p) if p.len() == 2 => Some((pos, p)),
+                _ => None,
+            })
+            .collect();
+
+        for (pos, pencilmarks) in &cells {
+            for x in *pencilmarks {
+                self.search(&cells, &mut vec![*pos], other(*pencilmarks, x), x, &mut res);
+            }
+        }
+
+        res
+    }
+
+    fn difficulty(&self) -> u32 {
+        310
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::methods::test_helpful_method;
+
+    use super::*;
+
+    #[test]
+    fn xy_chain() {
+        let grid = Grid::from_candidate_grid(
+            "
+            .----------------.---------------.----------.
+            | 3     7    2   | 1     9    5  | 8   4  6 |
+            | 8     9    4   | 3     6    7  | 5   2  1 |
+            | 1     5    6   | 28    4    28 | 7   9  3 |
+            :----------------+---------------+----------:
+            | 5     6    17  | 47    3    14 | 2   8  9 |
+            | 279   23   379 | 5     278  28 | 1   6  4 |
+            | 24    248  18  | 6     12   9  | 3   7  5 |
+            :----------------+---------------+----------:
+            | 6     1    89  | 4789  78   3  | 49  5  2 |
+            | 249   248  5   | 2489  128  14 | 6   3  7 |
+            | 2479  234  379 | 249   5    6  | 49  1  8 |
+            '----------------'---------------'----------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            XYChain::new(),
+            BTreeSet::from([Action::RemovePencilmarks {
+                figure: vec![57].into(),
+                pencilmarks: vec![4],
+            }]),
+        );
+
+        let grid = Grid::from_candidate_grid(
+            "
+            .------------.-------------------.------------------.
+            | 4  27   1  | 678   268   5     | 9     3     68   |
+            | 5  8    6  | 3     9     4     | 12    7     12   |
+            | 9  237  37 | 678   1     278   | 48    5     468  |
+            :------------+-------------------+------------------:
+            | 8  4    57 | 9     23    123   | 6     12    57   |
+            | 6  17   9  | 18    5     28    | 3     4     127  |
+            | 3  15   2  | 4     7     6     | 18    189   1589 |
+            :------------+-------------------+------------------:
+            | 2  356  4  | 1678  368   13789 | 1578  689   189  |
+            | 1  36   38 | 5     3468  379   | 2478  2689  2489 |
+            | 7  9    58 | 2     468   18    | 1458  168   3    |
+            '------------'-------------------'------------------'
+            ",
+        )
+        .unwrap();
+
+        test_helpful_method(
+            &grid,
+            XYChain::new(),
+            BTreeSet::from([
+                Action::RemovePencilmarks {
+                    figure: vec![67].into(),
+                    pencilmarks: vec![3],
+                },
+                Action::RemovePencilmarks {
+                    figure: vec![69].into(),
+                    pencilmarks: vec![8],
+                },
+            ]),
+        );
+
+        // Chains of three cells are Y-Wings, which are already applied
+        test_helpful_method(&grid, XYChain::new().max_length(3), BTreeSet::new());
+    }
+}
@@ -0,0 +1,237 @@
+use std::{
+    fmt::Debug,
+    ops::{Add, AddAssign, Sub, SubAssign},
+};
+
+/// Set of numbers 1-9, possible in the cell.
+/// Stored as 9-bit mask, so it can be copied and compared without allocations.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Default)]
+pub struct Pencilmarks {
+    // Bit `n` is set if number `n` is possible. Bit 0 is never set.
+    mask: u16,
+}
+
+impl Pencilmarks {
+    const ALL_MASK: u16 = 0b11_1111_1110;
+
+    pub const fn empty() -> Self {
+        Self { mask: 0 }
+    }
+
+    pub const fn all() -> Self {
+        Self {
+            mask: Self::ALL_MASK,
+        }
+    }
+
+    /// Returns pencilmarks from the mask, where bit `n` means number `n`.
+    /// Bits outside of 1-9 are ignored.
+    pub const fn from_mask(mask: u16) -> Self {
+        Self {
+            mask: mask & Self::ALL_MASK,
+        }
+    }
+
+    pub const fn mask(&self) -> u16 {
+        self.mask
+    }
+
+    /// Takes reference, so it can be used like `Vec::contains`.
+    pub const fn contains(&self, number: &u8) -> bool {
+        *number <= 9 && self.mask & (1 << *number) != 0
+    }
+
+    /// Panics if number is not in 1-9.
+    pub fn insert(&mut self, number: u8) {
+        assert!((1..=9).contains(&number), "pencilmark must be in 1-9");
+        self.mask |= 1 << number;
+    }
+
+    pub fn remove(&mut self, number: u8) {
+        if number <= 9 {
+            self.mask &= !(1 << number);
+        }
+    }
+
+    /// Returns the number of pencilmarks.
+    pub const fn len(&self) -> usize {
+        self.mask.count_ones() as usize
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.mask == 0
+    }
+
+    /// Returns numbers in ascending order.
+    pub fn iter(&self) -> IntoIter {
+        self.into_iter()
+    }
+
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.iter().collect()
+    }
+
+    /// Returns pencilmarks, present in both sets.
+    pub const fn intersection(&self, other: Pencilmarks) -> Pencilmarks {
+        Pencilmarks {
+            mask: self.mask & other.mask,
+        }
+    }
+}
+
+impl Debug for Pencilmarks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Add for Pencilmarks {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, rhs: Self) -> Self::Output {
+        Pencilmarks {
+            mask: self.mask | rhs.mask,
+        }
+    }
+}
+
+impl AddAssign for Pencilmarks {
+    #[allow(clippy::suspicious_op_assign_impl)]
+    fn add_assign(&mut self, rhs: Self) {
+        self.mask |= rhs.mask;
+    }
+}
+
+impl Sub for Pencilmarks {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Pencilmarks {
+            mask: self.mask & !rhs.mask,
+        }
+    }
+}
+
+impl SubAssign for Pencilmarks {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.mask &= !rhs.mask;
+    }
+}
+
+/// Iterator over numbers of [`Pencilmarks`] in ascending order.
+#[derive(Debug, Clone)]
+pub struct IntoIter {
+    mask: u16,
+}
+
+impl Iterator for IntoIter {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.mask == 0 {
+            return None;
+        }
+
+        let number = self.mask.trailing_zeros() as u8;
+        self.mask &= self.mask - 1;
+
+        Some(number)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.mask.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl IntoIterator for Pencilmarks {
+    type Item = u8;
+
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { mask: self.mask }
+    }
+}
+
+impl IntoIterator for &Pencilmarks {
+    type Item = u8;
+
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { mask: self.mask }
+    }
+}
+
+impl FromIterator<u8> for Pencilmarks {
+    /// Panics if some number is not in 1-9.
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        let mut res = Pencilmarks::empty();
+
+        for number in iter {
+            res.insert(number);
+        }
+
+        res
+    }
+}
+
+impl From<Vec<u8>> for Pencilmarks {
+    /// Panics if some number is not in 1-9.
+    fn from(value: Vec<u8>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl From<&[u8]> for Pencilmarks {
+    /// Panics if some number is not in 1-9.
+    fn from(value: &[u8]) -> Self {
+        value.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_and_remove() {
+        let mut pencilmarks = Pencilmarks::empty();
+
+        pencilmarks.insert(9);
+        pencilmarks.insert(1);
+        pencilmarks.insert(5);
+        pencilmarks.remove(5);
+        pencilmarks.remove(7);
+
+        assert_eq!(pencilmarks.to_vec(), vec![1, 9]);
+        assert_eq!(pencilmarks.len(), 2);
+        assert!(pencilmarks.contains(&9));
+        assert!(!pencilmarks.contains(&5));
+        assert!(!pencilmarks.contains(&0));
+        assert!(!pencilmarks.contains(&10));
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        Pencilmarks::empty().insert(0);
+    }
+
+    #[test]
+    fn set_operations() {
+        let a: Pencilmarks = vec![1, 2, 3].into();
+        let b: Pencilmarks = vec![3, 4].into();
+
+        assert_eq!(a + b, vec![1, 2, 3, 4].into());
+        assert_eq!(a - b, vec![1, 2].into());
+        assert_eq!(a.intersection(b), vec![3].into());
+        assert_eq!(Pencilmarks::all().len(), 9);
+        assert!(Pencilmarks::from_mask(1).is_empty());
+        assert_eq!(format!("{:?}", a), "[1, 2, 3]");
+    }
+}
@@ -0,0 +1,383 @@
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    cell::{Cell, Origin},
+    figure::Figure,
+    grid::Grid,
+};
+
+/// Bit `n - 1` is set if number `n` is allowed.
+const ALL_NUMBERS: u16 = 0b1_1111_1111;
+
+/// Backtracking search over the bitmasks of the grid.
+/// Respects numbers and pencilmarks of the grid it was created from.
+#[derive(Clone, Debug)]
+struct Search {
+    numbers: [u8; 81],
+    allowed: [u16; 81],
+    rows: [u16; 9],
+    cols: [u16; 9],
+    sqrs: [u16; 9],
+}
+
+impl Search {
+    /// Returns `None` if numbers of the grid already contradict each other
+    /// or are outside of 1-9.
+    fn new(grid: &Grid) -> Option<Self> {
+        let mut search = Search {
+            numbers: [0; 81],
+            allowed: [ALL_NUMBERS; 81],
+            rows: [0; 9],
+            cols: [0; 9],
+            sqrs: [0; 9],
+        };
+
+        for pos in Figure::all_cells() {
+            match &grid[pos] {
+                Cell::Number(n) => {
+                    if !(1..=9).contains(n) || search.candidates(pos) & (1 << (n - 1)) == 0 {
+                        return None;
+                    }
+                    search.place(pos, *n);
+                }
+                Cell::Pencilmarks(pencilmarks) => {
+                    search.allowed[pos] = pencilmarks.mask() >> 1;
+                }
+            }
+        }
+
+        Some(search)
+    }
+
+    fn candidates(&self, pos: usize) -> u16 {
+        let used = self.rows[Figure::row_of(pos) as usize]
+            | self.cols[Figure::col_of(pos) as usize]
+            | self.sqrs[Figure::sqr_of(pos) as usize];
+
+        self.allowed[pos] & !used
+    }
+
+    fn toggle(&mut self, pos: usize, number: u8) {
+        let bit = 1 << (number - 1);
+
+        self.rows[Figure::row_of(pos) as usize] ^= bit;
+        self.cols[Figure::col_of(pos) as usize] ^= bit;
+        self.sqrs[Figure::sqr_of(pos) as usize] ^= bit;
+    }
+
+    fn place(&mut self, pos: usize, number: u8) {
+        self.numbers[pos] = number;
+        self.toggle(pos, number);
+    }
+
+    fn unplace(&mut self, pos: usize) {
+        let number = self.numbers[pos];
+        self.numbers[pos] = 0;
+        self.toggle(pos, number);
+    }
+
+    /// Returns empty position with the fewest candidates.
+    /// Returns `None` if every cell is filled.
+    fn most_constrained(&self) -> Option<(usize, u16)> {
+        let mut best: Option<(usize, u16)> = None;
+
+        for pos in 0..81 {
+            if self.numbers[pos] != 0 {
+                continue;
+            }
+
+            let candidates = self.candidates(pos);
+
+            if best.is_none_or(|(_, c)| candidates.count_ones() < c.count_ones()) {
+                best = Some((pos, candidates));
+
+                if candidates.count_ones() <= 1 {
+                    break;
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Depth-first search. Stops when `solutions` has `limit` elements.
+    fn run(&mut self, limit: usize, solutions: &mut Vec<[u8; 81]>) {
+        if solutions.len() >= limit {
+            return;
+        }
+
+        let Some((pos, mut candidates)) = self.most_constrained() else {
+            solutions.push(self.numbers);
+            return;
+        };
+
+        while candidates != 0 {
+            let number = candidates.trailing_zeros() as u8 + 1;
+            candidates &= candidates - 1;
+
+            self.place(pos, number);
+            self.run(limit, solutions);
+            self.unplace(pos);
+
+            if solutions.len() >= limit {
+                return;
+            }
+        }
+    }
+
+    /// Depth-first search, trying numbers in random order.
+    /// Returns `true` if solution was found (it is left in `self.numbers`).
+    fn run_shuffled<R: Rng>(&mut self, rng: &mut R) -> bool {
+        let Some((pos, candidates)) = self.most_constrained() else {
+            return true;
+        };
+
+        let mut numbers: Vec<u8> = (1..=9)
+            .filter(|n| candidates & (1 << (n - 1)) != 0)
+            .collect();
+        numbers.shuffle(rng);
+
+        for number in numbers {
+            self.place(pos, number);
+
+            if self.run_shuffled(rng) {
+                return true;
+            }
+
+            self.unplace(pos);
+        }
+
+        false
+    }
+}
+
+/// Converts found numbers back to the grid, keeping the numbers of the original grid.
+fn to_grid(grid: &Grid, numbers: &[u8; 81]) -> Grid {
+    let mut res = grid.clone();
+
+    for pos in Figure::all_cells() {
+        if res[pos].is_pencilmarks() {
+            res.place_number(pos, numbers[pos], Origin::Solver);
+        }
+    }
+
+    res
+}
+
+/// Result of the search for the grid's solutions.
+#[derive(Debug, PartialEq, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum Solutions {
+    NoSolution,
+    Unique(Grid),
+    /// Contains two different solutions of the grid.
+    Multiple(Grid, Grid),
+}
+
+/// Returns numbers of up to `limit` solutions of the grid.
+fn find_solutions(grid: &Grid, limit: usize) -> Vec<[u8; 81]> {
+    let mut solutions = vec![];
+
+    if let Some(mut search) = Search::new(grid) {
+        search.run(limit, &mut solutions);
+    }
+
+    solutions
+}
+
+/// Finds solution of the grid by trying every possibility.
+/// Pencilmarks of the grid are respected: number will be placed in the cell only
+/// if it is one of the cell's pencilmarks.
+/// Returns `None` if the grid has no solution.
+/// If the grid has multiple solutions, returns one of them.
+pub fn solve(grid: &Grid) -> Option<Grid> {
+    find_solutions(grid, 1)
+        .first()
+        .map(|numbers| to_grid(grid, numbers))
+}
+
+/// Same as [`solve`], but numbers are tried in random order,
+/// so for grid with multiple solutions a random one is returned.
+/// For example, random full grid can be generated from the empty one.
+pub fn solve_random<R: Rng>(grid: &Grid, rng: &mut R) -> Option<Grid> {
+    let mut search = Search::new(grid)?;
+
+    if search.run_shuffled(rng) {
+        Some(to_grid(grid, &search.numbers))
+    } else {
+        None
+    }
+}
+
+/// Counts solutions of the grid, but stops counting after `limit` solutions found.
+/// Pencilmarks of the grid are respected, the same way as in [`solve`].
+pub fn count_solutions(grid: &Grid, limit: usize) -> usize {
+    find_solutions(grid, limit).len()
+}
+
+/// Searches for the first two solutions of the grid.
+/// Pencilmarks of the grid are respected, the same way as in [`solve`].
+pub fn solutions(grid: &Grid) -> Solutions {
+    let found = find_solutions(grid, 2);
+
+    match found.as_slice() {
+        [] => Solutions::NoSolution,
+        [unique] => Solutions::Unique(to_grid(grid, unique)),
+        [first, second, ..] => Solutions::Multiple(to_grid(grid, first), to_grid(grid, second)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn solve_easy() {
+        let grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        assert_eq!(
+            solve(&grid),
+            Some(
+                Grid::from_str(
+                    "735164928426978315198532674249381756387256149561749832852617493914823567673495281"
+                )
+                .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn solve_hard() {
+        let grid = Grid::from_str(
+            "800000000003600000070090200050007000000045700000100030001000068008500010090000400",
+        )
+        .unwrap();
+
+        assert_eq!(
+            solve(&grid),
+            Some(
+                Grid::from_str(
+                    "812753649943682175675491283154237896369845721287169534521974368438526917796318452"
+                )
+                .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn solve_respects_pencilmarks() {
+        let mut grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        // Only solution has 7 here
+        grid.set_pencilmarks(0, vec![3, 5]);
+
+        assert_eq!(solve(&grid), None);
+    }
+
+    #[test]
+    fn solve_contradicting_numbers() {
+        let grid = Grid::from_str(
+            "770004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        assert_eq!(solve(&grid), None);
+    }
+
+    #[test]
+    fn numbers_out_of_range() {
+        let mut grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+        grid.set_number(0, 0);
+
+        assert_eq!(solve(&grid), None);
+        assert_eq!(count_solutions(&grid, 2), 0);
+        assert_eq!(solutions(&grid), Solutions::NoSolution);
+        assert!(!grid.has_unique_solution());
+        assert_eq!(solve_random(&grid, &mut StdRng::seed_from_u64(42)), None);
+
+        grid.set_number(0, 10);
+        assert_eq!(count_solutions(&grid, 2), 0);
+    }
+
+    #[test]
+    fn solve_random_is_valid() {
+        let empty = Grid::from_str(&"0".repeat(81)).unwrap();
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let first = solve_random(&empty, &mut rng).unwrap();
+        let second = solve_random(&empty, &mut rng).unwrap();
+
+        assert!(first.is_solved());
+        assert_ne!(first, second);
+        assert_eq!(count_solutions(&first, 2), 1);
+
+        let puzzle = Grid::from_str(
+            "800000000003600000070090200050007000000045700000100030001000068008500010090000400",
+        )
+        .unwrap();
+        assert_eq!(solve_random(&puzzle, &mut rng), solve(&puzzle));
+    }
+
+    #[test]
+    fn count() {
+        let unique = Grid::from_str(
+            "800000000003600000070090200050007000000045700000100030001000068008500010090000400",
+        )
+        .unwrap();
+        assert_eq!(count_solutions(&unique, 10), 1);
+
+        let empty = Grid::from_str(&"0".repeat(81)).unwrap();
+        assert_eq!(count_solutions(&empty, 10), 10);
+
+        let contradicting = Grid::from_str(
+            "770004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+        assert_eq!(count_solutions(&contradicting, 10), 0);
+    }
+
+    #[test]
+    fn solutions_kind() {
+        let unique = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+        assert_eq!(
+            solutions(&unique),
+            Solutions::Unique(
+                Grid::from_str(
+                    "735164928426978315198532674249381756387256149561749832852617493914823567673495281"
+                )
+                .unwrap()
+            )
+        );
+
+        // Same grid, but without 8 in the top right corner
+        let multiple = Grid::from_str(
+            "000004020406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+        let Solutions::Multiple(first, second) = solutions(&multiple) else {
+            panic!("grid should have multiple solutions");
+        };
+        assert_ne!(first, second);
+        assert!(first.is_solved() && second.is_solved());
+
+        let mut no_solution = unique.clone();
+        no_solution.set_pencilmarks(0, vec![3, 5]);
+        assert_eq!(solutions(&no_solution), Solutions::NoSolution);
+    }
+}
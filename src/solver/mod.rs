@@ -0,0 +1,507 @@
+pub mod brute_force;
+pub mod trace;
+
+use std::{any::Any, collections::BTreeSet, fmt::Display};
+
+use crate::{
+    action::Action,
+    grid::{Conflict, Grid},
+    methods::{aic::Aic, als::AlmostLockedSets, registry, xy_chain::XYChain, Method},
+};
+
+use self::trace::{SolveStep, SolveTrace};
+
+#[derive(Clone, Debug)]
+pub struct Solver {
+    methods: Vec<(Box<dyn Method>, bool)>,
+    uniqueness_methods: bool,
+}
+
+impl Solver {
+    /// Returns solver with all methods in the sudokuwiki.org order
+    /// (see [`registry`]).
+    pub fn all_methods() -> Self {
+        Solver {
+            methods: registry::all()
+                .map(|method| (method.create(), true))
+                .collect(),
+            uniqueness_methods: true,
+        }
+    }
+
+    /// Returns enabled methods (in order).
+    /// Skips uniqueness based methods, if they are turned off.
+    fn enabled_methods(&self) -> impl Iterator<Item = &dyn Method> {
+        self.methods
+            .iter()
+            .filter(|(method, enabled)| {
+                *enabled && (self.uniqueness_methods || !method.is_uniqueness_based())
+            })
+            .map(|(method, _)| method.as_ref())
+    }
+
+    /// Goes through all enabled methods (in order).
+    /// If `stop_after_first` is true: returns Vec of helpful actions from every method.
+    /// Else: returns helpful and simplified applications from the first applicable method.
+    pub fn take_step(&self, grid: &Grid, stop_after_first: bool) -> BTreeSet<Action> {
+        let mut applications = BTreeSet::new();
+        for method in self.enabled_methods() {
+            let method_applications = method.get_all_helpful_applications(grid, true);
+
+            if !method_applications.is_empty() {
+                applications.extend(method_applications);
+
+                if stop_after_first {
+                    break;
+                }
+            }
+        }
+
+        applications
+    }
+
+    /// Goes through all enabled methods (in order).
+    /// Returns the first method, applicable to the given grid, with its helpful and simplified actions.
+    /// Returns `None` if no enabled method can help.
+    pub fn first_applicable(&self, grid: &Grid) -> Option<(&dyn Method, BTreeSet<Action>)> {
+        self.enabled_methods()
+            .map(|method| (method, method.get_all_helpful_applications(grid, true)))
+            .find(|(_, applications)| !applications.is_empty())
+    }
+
+    /// Goes through all enabled methods (in order).
+    /// Applies the first applicable methods for the given grid.
+    /// If method returns multiple actions: applies them in method's order.
+    /// Returns [`SolveTrace`] of steps taken and grid (which might be unsloved).
+    /// If `keep_snapshots` is true: every step stores the grid before it.
+    ///
+    /// Grid is validated before solving and after every step.
+    /// On contradiction returns [`SolveError`] with steps taken so far
+    /// (the step, which led to contradiction, is included).
+    pub fn try_solve(
+        &self,
+        grid: &mut Grid,
+        keep_snapshots: bool,
+    ) -> Result<SolveTrace, SolveError> {
+        let mut trace = SolveTrace::new();
+
+        if let Err(conflicts) = grid.validate() {
+            return Err(SolveError { trace, conflicts });
+        }
+
+        while !grid.is_solved() {
+            let Some((method, actions)) = self.first_applicable(grid) else {
+                break;
+            };
+
+            let grid_before = keep_snapshots.then(|| grid.clone());
+
+            for action in &actions {
+                action.apply_to_grid(grid);
+            }
+
+            trace.push(SolveStep::new(
+                method.to_string(),
+                method.difficulty(),
+                actions.into_iter().collect(),
+                grid_before,
+            ));
+
+            if let Err(conflicts) = grid.validate() {
+                return Err(SolveError { trace, conflicts });
+            }
+        }
+
+        Ok(trace)
+    }
+
+    /// Returns methods and bool, indicating whether given method is enabled.
+    pub fn methods(&self) -> &Vec<(Box<dyn Method>, bool)> {
+        &self.methods
+    }
+
+    /// Enables or disables method at given position.
+    pub fn set_state(&mut self, index: usize, state: bool) {
+        self.methods[index].1 = state;
+    }
+
+    /// Turns on or off every method, which assumes that the puzzle has unique solution
+    /// (see [`Method::is_uniqueness_based`]). Turn them off for puzzles with multiple solutions.
+    /// Turned off methods are skipped, even if they are enabled.
+    pub fn set_uniqueness_methods(&mut self, state: bool) {
+        self.uniqueness_methods = state;
+    }
+
+    /// Returns `true` if uniqueness based methods can be used.
+    pub fn uniqueness_methods(&self) -> bool {
+        self.uniqueness_methods
+    }
+
+    /// Sets maximum number of cells in the chain for XY-Chain (see [`XYChain::max_length`]).
+    /// Lower it to bound the solving time.
+    pub fn set_xy_chain_max_length(&mut self, max_length: usize) {
+        self.update_methods(|chain: &XYChain| chain.clone().max_length(max_length));
+    }
+
+    /// Sets maximum number of links in the chain for AIC (see [`Aic::max_length`]).
+    /// Lower it to bound the solving time.
+    pub fn set_aic_max_length(&mut self, max_length: usize) {
+        self.update_methods(|aic: &Aic| aic.clone().max_length(max_length));
+    }
+
+    /// Sets maximum number of cells in the set for the methods on Almost Locked Sets
+    /// (see [`AlmostLockedSets::max_size`]). Lower it to bound the solving time.
+    pub fn set_als_max_size(&mut self, max_size: usize) {
+        self.update_methods(|als: &AlmostLockedSets| als.clone().max_size(max_size));
+    }
+
+    /// Replaces the method at given position. Its enabled state is kept.
+    pub fn replace_method(&mut self, index: usize, method: Box<dyn Method>) {
+        self.methods[index].0 = method;
+    }
+
+    /// Replaces every method of type `T` with its updated copy. Enabled state is kept.
+    fn update_methods<T: Method + 'static>(&mut self, update: impl Fn(&T) -> T) {
+        for (method, _) in &mut self.methods {
+            if let Some(old) = method.as_any().downcast_ref::<T>() {
+                *method = Box::new(update(old));
+            }
+        }
+    }
+
+    /// Toggles the method at given position.
+    pub fn toggle(&mut self, index: usize) {
+        let current_state = self.methods[index].1;
+
+        self.set_state(index, !current_state);
+    }
+}
+
+impl PartialEq for Solver {
+    fn eq(&self, other: &Self) -> bool {
+        self.methods
+            .iter()
+            .zip(other.methods.iter())
+            .all(|(a, b)| a.type_id() == b.type_id())
+    }
+}
+
+/// Error, returned by [`Solver::try_solve`], when the grid has contradiction.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SolveError {
+    trace: SolveTrace,
+    conflicts: Vec<Conflict>,
+}
+
+impl SolveError {
+    /// Steps, made before the contradiction was found.
+    pub fn trace(&self) -> &SolveTrace {
+        &self.trace
+    }
+
+    pub fn into_trace(self) -> SolveTrace {
+        self.trace
+    }
+
+    pub fn conflicts(&self) -> &Vec<Conflict> {
+        &self.conflicts
+    }
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Grid has contradiction after {} steps", self.trace.len())?;
+
+        for conflict in &self.conflicts {
+            write!(f, "\n{}", conflict)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::figure::Figure;
+
+    use super::*;
+
+    #[test]
+    fn take_step() {
+        let solver = Solver::all_methods();
+
+        {
+            let easiest_grid = Grid::from_str(
+                "300967001040302080020000070070000090000873000500010003004705100905000207800621004",
+            )
+            .unwrap();
+
+            let actions = solver.take_step(&easiest_grid, true);
+            let predictions = BTreeSet::from([
+                Action::PlaceNumber {
+                    position: 2,
+                    number: 8,
+                },
+                Action::PlaceNumber {
+                    position: 13,
+                    number: 5,
+                },
+                Action::PlaceNumber {
+                    position: 66,
+                    number: 4,
+                },
+                Action::PlaceNumber {
+                    position: 73,
+                    number: 3,
+                },
+            ]);
+
+            assert_eq!(actions, predictions);
+        }
+        {
+            let multiple_actions_grid = Grid::from_str(
+                "000000700007109000680070010001090600000300020040000003008060100500000040000002005",
+            )
+            .unwrap();
+
+            let actions = solver.take_step(&multiple_actions_grid, false);
+            let predictions = BTreeSet::from([
+                Action::PlaceNumber {
+                    position: 44,
+                    number: 1,
+                },
+                Action::RemovePencilmarks {
+                    figure: vec![7, 8].into(),
+                    pencilmarks: vec![6],
+                },
+                Action::RemovePencilmarks {
+                    figure: vec![40, 41].into(),
+                    pencilmarks: vec![1],
+                },
+                // X-Cycles
+                Action::RemovePencilmarks {
+                    figure: vec![7].into(),
+                    pencilmarks: vec![6],
+                },
+                Action::RemovePencilmarks {
+                    figure: vec![8].into(),
+                    pencilmarks: vec![6],
+                },
+                Action::RemovePencilmarks {
+                    figure: vec![40].into(),
+                    pencilmarks: vec![1],
+                },
+                Action::RemovePencilmarks {
+                    figure: vec![41].into(),
+                    pencilmarks: vec![1],
+                },
+                // Almost Locked Sets
+                Action::RemovePencilmarks {
+                    figure: vec![44].into(),
+                    pencilmarks: vec![7],
+                },
+                Action::RemovePencilmarks {
+                    figure: vec![44].into(),
+                    pencilmarks: vec![8],
+                },
+                Action::RemovePencilmarks {
+                    figure: vec![44].into(),
+                    pencilmarks: vec![9],
+                },
+            ]);
+
+            assert_eq!(actions, predictions);
+        }
+        {
+            let mut hard_grid = Grid::from_str(
+                "000000700007109000680070010001090600000300021040000003008060100500000040000002005",
+            )
+            .unwrap();
+
+            hard_grid.set_pencilmarks(7, vec![3, 5, 8, 9]);
+            hard_grid.set_pencilmarks(8, vec![2, 4, 8, 9]);
+
+            let actions = solver.take_step(&hard_grid, true);
+
+            assert_eq!(actions, BTreeSet::new());
+        }
+    }
+
+    #[test]
+    fn xy_chain_max_length() {
+        let mut solver = Solver::all_methods();
+        let index = solver
+            .methods()
+            .iter()
+            .position(|(method, _)| method.to_string() == "XY-Chain")
+            .unwrap();
+
+        solver.set_state(index, false);
+        solver.set_xy_chain_max_length(3);
+
+        let (method, enabled) = &solver.methods()[index];
+        assert_eq!(format!("{:?}", method), "XYChain { max_length: 3 }");
+        assert!(!enabled);
+    }
+
+    #[test]
+    fn aic_max_length() {
+        let mut solver = Solver::all_methods();
+        let index = solver
+            .methods()
+            .iter()
+            .position(|(method, _)| method.to_string() == "Alternating Inference Chain")
+            .unwrap();
+
+        solver.set_state(index, false);
+        solver.set_aic_max_length(4);
+
+        let (method, enabled) = &solver.methods()[index];
+        assert_eq!(format!("{:?}", method), "Aic { max_length: 4 }");
+        assert!(!enabled);
+    }
+
+    #[test]
+    fn als_max_size() {
+        let mut solver = Solver::all_methods();
+        let index = solver
+            .methods()
+            .iter()
+            .position(|(method, _)| method.to_string() == "ALS-XY-Wing")
+            .unwrap();
+
+        solver.set_state(index, false);
+        solver.set_als_max_size(3);
+
+        let sizes: Vec<String> = solver
+            .methods()
+            .iter()
+            .map(|(method, _)| format!("{:?}", method))
+            .filter(|method| method.starts_with("AlmostLockedSets"))
+            .collect();
+
+        assert_eq!(
+            sizes,
+            [
+                "AlmostLockedSets { pattern: XZ, max_size: 3 }",
+                "AlmostLockedSets { pattern: XYWing, max_size: 3 }",
+                "AlmostLockedSets { pattern: DeathBlossom, max_size: 3 }",
+            ]
+        );
+        assert!(!solver.methods()[index].1);
+    }
+
+    #[test]
+    fn replace_method() {
+        let mut solver = Solver::all_methods();
+        let index = solver
+            .methods()
+            .iter()
+            .position(|(method, _)| method.to_string() == "XY-Chain")
+            .unwrap();
+
+        solver.set_state(index, false);
+        solver.replace_method(index, Box::new(XYChain::new().max_length(5)));
+
+        let (method, enabled) = &solver.methods()[index];
+        assert_eq!(format!("{:?}", method), "XYChain { max_length: 5 }");
+        assert!(!enabled);
+    }
+
+    #[test]
+    fn try_solve() {
+        let mut grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+        Solver::all_methods().try_solve(&mut grid, false).unwrap();
+
+        assert_eq!(
+            grid,
+            Grid::from_str(
+                "735164928426978315198532674249381756387256149561749832852617493914823567673495281"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn try_solve_trace() {
+        let start = Grid::from_str(
+            "100000569492056108056109240009640801064010000218035604040500016905061402621000005",
+        )
+        .unwrap();
+
+        let mut grid = start.clone();
+        let trace = Solver::all_methods().try_solve(&mut grid, true).unwrap();
+
+        assert!(grid.is_solved());
+        assert_eq!(trace.steps()[0].grid_before(), Some(&start));
+        assert!(trace
+            .steps()
+            .iter()
+            .any(|step| step.method() == "X-Wing" && step.difficulty() == 200));
+
+        // Every snapshot is the previous snapshot with previous step applied
+        for (previous, next) in trace.steps().iter().zip(trace.steps().iter().skip(1)) {
+            let mut replayed = previous.grid_before().unwrap().clone();
+
+            for action in previous.actions() {
+                action.apply_to_grid(&mut replayed);
+            }
+
+            assert_eq!(Some(&replayed), next.grid_before());
+        }
+
+        let mut replayed = start.clone();
+        trace.replay(&mut replayed);
+        assert_eq!(replayed, grid);
+
+        let mut without_snapshots = start.clone();
+        let trace = Solver::all_methods()
+            .try_solve(&mut without_snapshots, false)
+            .unwrap();
+        assert!(trace
+            .steps()
+            .iter()
+            .all(|step| step.grid_before().is_none()));
+    }
+
+    #[test]
+    fn try_solve_contradiction() {
+        let mut grid = Grid::from_str(
+            "000004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+
+        // Naked Single places 3 in both cells of the first row
+        grid.set_pencilmarks(1, vec![3]);
+        grid.set_pencilmarks(2, vec![3]);
+
+        let error = Solver::all_methods()
+            .try_solve(&mut grid, false)
+            .unwrap_err();
+
+        assert_eq!(error.trace().len(), 1);
+        assert_eq!(error.trace().steps()[0].method(), "Naked Single");
+        assert!(error.conflicts().contains(&Conflict::DuplicateNumber {
+            unit: Figure::row(0),
+            cells: vec![1, 2].into(),
+            number: 3,
+        }));
+
+        let mut invalid = Grid::from_str(
+            "600004028406000005100030600000301000087000140000709000002010003900000507670400000",
+        )
+        .unwrap();
+        let error = Solver::all_methods()
+            .try_solve(&mut invalid, false)
+            .unwrap_err();
+
+        assert!(error.trace().is_empty());
+    }
+}
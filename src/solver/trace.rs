@@ -0,0 +1,89 @@
+use crate::{action::Action, grid::Grid};
+
+/// One step of the solve: every action, produced by one method at once.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SolveStep {
+    method: String,
+    difficulty: u32,
+    actions: Vec<Action>,
+    grid_before: Option<Grid>,
+}
+
+impl SolveStep {
+    pub fn new(
+        method: String,
+        difficulty: u32,
+        actions: Vec<Action>,
+        grid_before: Option<Grid>,
+    ) -> Self {
+        Self {
+            method,
+            difficulty,
+            actions,
+            grid_before,
+        }
+    }
+
+    /// Name of the method (its `Display`), which produced this step.
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// Difficulty of the method, which produced this step (see [`crate::methods::Method::difficulty`]).
+    pub fn difficulty(&self) -> u32 {
+        self.difficulty
+    }
+
+    /// Actions in order they were applied.
+    pub fn actions(&self) -> &Vec<Action> {
+        &self.actions
+    }
+
+    /// Grid before the step was applied.
+    /// Is `Some` only if solver was asked to keep snapshots.
+    pub fn grid_before(&self) -> Option<&Grid> {
+        self.grid_before.as_ref()
+    }
+}
+
+/// Ordered list of steps, made by [`super::Solver`].
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SolveTrace {
+    steps: Vec<SolveStep>,
+}
+
+impl SolveTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, step: SolveStep) {
+        self.steps.push(step);
+    }
+
+    pub fn steps(&self) -> &Vec<SolveStep> {
+        &self.steps
+    }
+
+    /// Returns all actions of every step (in order).
+    pub fn actions(&self) -> impl Iterator<Item = &Action> {
+        self.steps.iter().flat_map(|step| step.actions.iter())
+    }
+
+    /// Applies every step to the grid (in order).
+    pub fn replay(&self, grid: &mut Grid) {
+        for action in self.actions() {
+            action.apply_to_grid(grid);
+        }
+    }
+
+    /// Returns the number of steps.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns true if solver did not make any step.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}